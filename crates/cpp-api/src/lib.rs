@@ -24,7 +24,7 @@ impl AdjacencyListCpp {
 
         for (i, row) in matrix.0.iter().enumerate() {
             for (j, &val) in row.iter().enumerate() {
-                if val == 1 {
+                if val != 0 {
                     adj_list.add_edge_unchecked(i, j);
                 }
             }
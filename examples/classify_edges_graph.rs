@@ -1,7 +1,7 @@
 use graphs_algorithms::Graph;
 use graphs_algorithms::graphs::AdjacencyList;
 use graphs_algorithms::utils::print_tip;
-use graphs_algorithms::{Edge, GraphIO, print_list};
+use graphs_algorithms::{Edge, ForestEvent, GraphIO, print_list};
 
 fn main() {
     let digraph = AdjacencyList(vec![
@@ -19,22 +19,16 @@ fn main() {
     println!("Digraph: ");
     print_list(&digraph);
 
-    let print_edge = |e| match e {
-        Edge::Tree(v, u) => println!("Tree: {} -> {}", v, u),
-        Edge::Back(v, u) | Edge::ParentBack(v, u) => {
-            println!("Back: {} -> {}", v, u)
+    for event in digraph.classify_all_edges() {
+        match event {
+            ForestEvent::Root(v) => println!("Root: {}", v),
+            ForestEvent::Event(Edge::Tree(v, u)) => println!("Tree: {} -> {}", v, u),
+            ForestEvent::Event(Edge::Back(v, u) | Edge::ParentBack(v, u)) => {
+                println!("Back: {} -> {}", v, u)
+            }
+            ForestEvent::Event(Edge::Forward(v, u)) => println!("Forward: {} -> {}", v, u),
+            ForestEvent::Event(Edge::Cross(v, u)) => println!("Cross: {} -> {}", v, u),
         }
-        Edge::Forward(v, u) => println!("Forward: {} -> {}", v, u),
-        Edge::Cross(v, u) => println!("Cross: {} -> {}", v, u),
-    };
-
-    let mut iter = digraph.classify_edges(0);
-    for e in &mut iter {
-        print_edge(e);
-    }
-    iter.new_start(8);
-    for e in &mut iter {
-        print_edge(e)
     }
 
     let path = "examples/dot/classify_edges/directed.dot";
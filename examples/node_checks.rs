@@ -31,7 +31,7 @@ fn main() {
         println!("Vertex {} has degree {}", i, ug1.undirected_node_degree(i));
     }
 
-    let ug2 = IncidenceMatrix(vec![vec![1, 1, 0], vec![0, 1, 1]]);
+    let ug2 = IncidenceMatrix::from_rows(vec![vec![1, 1, 0], vec![0, 1, 1]]);
 
     println!("Current undirected graph on incidence matrix: ");
     print_incidence_matrix(&ug2);
@@ -13,7 +13,7 @@ fn main() {
     let incidence = IncidenceMatrix::from_adjacency_matrix(&m1);
 
     println!("Incidence matrix: ");
-    for row in incidence.0 {
+    for row in incidence.rows() {
         for col in row {
             print!("{col} ");
         }
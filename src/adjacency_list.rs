@@ -1,4 +1,4 @@
-use crate::graph::{DfsEvent, UndirectedGraph};
+use crate::graph::UndirectedGraph;
 use crate::graph_io::UndirectedGraphIO;
 use crate::graphs::{AdjacencyMatrix, IncidenceMatrix};
 use crate::{Graph, GraphIO};
@@ -29,12 +29,19 @@ impl AdjacencyList {
 
     /// Constructs an adjacency list from an incidence matrix.
     ///
+    /// Directed rows (`-1`/`1`) become a single edge, undirected rows (`1`/`1`) become
+    /// an edge on each direction and self-loops are kept. Parallel edges are merged,
+    /// since an adjacency list holds at most one edge between two nodes.
+    ///
     /// # Arguments
     /// * `matrix` - Reference to the incidence matrix.
-    /// # Note
-    /// This function is currently unimplemented and definitely will panic if you call it.
-    pub fn from_incidence_matrix(_matrix: &IncidenceMatrix) -> Self {
-        todo!()
+    pub fn from_incidence_matrix(matrix: &IncidenceMatrix) -> Self {
+        let mut adjacency_list = AdjacencyList(vec![Vec::new(); matrix.order()]);
+
+        for (u, v) in matrix.directed_edges() {
+            adjacency_list.add_edge(u, v);
+        }
+        adjacency_list
     }
 }
 
@@ -107,38 +114,6 @@ impl Graph<usize> for AdjacencyList {
         }
     }
 
-    fn bipartite(&self) -> bool {
-        let n = self.order();
-        if n == 0 {
-            return true;
-        }
-
-        let mut side = vec![None; n];
-
-        for start in 0..n {
-            if side[start].is_some() {
-                continue;
-            }
-            side[start] = Some(0);
-            let mut queue = std::collections::VecDeque::new();
-            queue.push_back(start);
-
-            while let Some(u) = queue.pop_front() {
-                let u_side = side[u].unwrap();
-                for v in self.neighbors(u) {
-                    if side[v].is_none() {
-                        side[v] = Some(1 - u_side);
-                        queue.push_back(v);
-                    } else if side[v] == Some(u_side) {
-                        return false;
-                    }
-                }
-            }
-        }
-
-        true
-    }
-
     fn underlying_graph(&self) -> Self {
         let mut list = AdjacencyList(vec![Vec::new(); self.0.len()]);
 
@@ -173,20 +148,6 @@ impl UndirectedGraph<usize> for AdjacencyList {
         regular_edges / 2 + self_loops
     }
 
-    fn connected(&self) -> bool {
-        for i in 0..self.order() {
-            if self
-                .dfs(i)
-                .filter(|event| matches!(event, DfsEvent::Discover(_, _)))
-                .count()
-                != self.order()
-            {
-                return false;
-            }
-        }
-        true
-    }
-
     fn undirected_node_degree(&self, node: usize) -> usize {
         self.0
             .get(node)
@@ -199,6 +160,7 @@ impl GraphIO<usize> for AdjacencyList {}
 impl UndirectedGraphIO<usize> for AdjacencyList {}
 
 #[cfg(test)]
+#[allow(clippy::single_match)]
 mod tests {
 
     use std::io::{Error, ErrorKind};
@@ -279,6 +241,28 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn new_graph_with_extra_column() {
+        let name = format!("adjacency_list_extra_column_{}.txt", std::process::id());
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, "3\n1,2,5\n2,3,1,4\n").unwrap();
+
+        let directed: Result<AdjacencyList, Error> =
+            GraphIO::import_from_file(path.display().to_string());
+        let undirected: Result<AdjacencyList, Error> =
+            UndirectedGraphIO::import_undirected_from_file(path.display().to_string());
+        std::fs::remove_file(&path).unwrap();
+
+        for result in [directed, undirected] {
+            assert!(result.is_err());
+
+            if let Err(err) = result {
+                assert!(err.kind() == ErrorKind::InvalidData);
+                assert!(err.to_string().contains("Invalid edge was found"));
+            }
+        }
+    }
+
     #[test]
     fn connected_undirected_graph() {
         // Graph: 2 ── 0 ── 1
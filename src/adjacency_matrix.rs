@@ -1,10 +1,12 @@
-use crate::graph_io::UndirectedGraphIO;
+use crate::all_pairs::{self, AllPairsShortestPaths};
+use crate::graph_io::{UndirectedGraphIO, WeightedGraphIO};
 use crate::graphs::{AdjacencyList, IncidenceMatrix};
-use crate::{Graph, GraphIO, UndirectedGraph};
+use crate::{Graph, GraphIO, UndirectedGraph, WeightedGraph};
 
 /// Represents a graph using an adjacency matrix.
-/// Each row corresponds to a node, and each cell of it contains 0 or 1,
-/// indicating whether an edge exists between the row's node and the column's node.
+/// Each row corresponds to a node, and each cell of it contains 0 when there's no edge
+/// between the row's node and the column's node, or the weight of that edge otherwise.
+/// Unweighted edges are stored with weight 1.
 #[derive(Debug, Clone)]
 pub struct AdjacencyMatrix(pub Vec<Vec<usize>>);
 
@@ -27,12 +29,54 @@ impl AdjacencyMatrix {
 
     /// Constructs an adjacency matrix from an incidence matrix.
     ///
+    /// Directed rows (`-1`/`1`) become a single edge, undirected rows (`1`/`1`) become
+    /// an edge on each direction and self-loops are kept. Parallel edges are merged,
+    /// since each cell of the matrix holds at most one edge.
+    ///
     /// # Arguments
-    /// * `_matrix` - Reference to the incidence matrix.
-    /// # Note
-    /// This function is currently unimplemented and definitely will panic if you call it.
-    pub fn from_incidency_matrix(_matrix: &IncidenceMatrix) -> Self {
-        todo!()
+    /// * `matrix` - Reference to the incidence matrix.
+    pub fn from_incidency_matrix(matrix: &IncidenceMatrix) -> Self {
+        let n = matrix.order();
+        let mut adjacency_matrix = AdjacencyMatrix(vec![vec![0; n]; n]);
+
+        for (u, v) in matrix.directed_edges() {
+            adjacency_matrix.add_edge(u, v);
+        }
+        adjacency_matrix
+    }
+
+    /// Computes the shortest paths between every pair of nodes
+    /// using the **Floyd-Warshall algorithm** over the rows of the matrix.
+    ///
+    /// The weights are unsigned, so there's never a negative cycle and a shortest path
+    /// exists between every pair of connected nodes.
+    pub fn floyd_warshall(&self) -> AllPairsShortestPaths<usize> {
+        all_pairs::floyd_warshall(
+            self.0
+                .iter()
+                .map(|row| row.iter().map(|&w| (w != 0).then_some(w)).collect())
+                .collect(),
+        )
+        .expect("unsigned weights can't form a negative cycle")
+    }
+
+    /// Computes the **transitive closure** of the graph, where there's an edge
+    /// `(u -> v)` whenever `v` can be reached from `u` through at least one edge.
+    ///
+    /// A node only gets a self-loop if it lies on a cycle.
+    pub fn transitive_closure(&self) -> Self {
+        let edges: Vec<Vec<bool>> = self
+            .0
+            .iter()
+            .map(|row| row.iter().map(|&w| w != 0).collect())
+            .collect();
+
+        AdjacencyMatrix(
+            all_pairs::transitive_closure(&edges)
+                .into_iter()
+                .map(|row| row.into_iter().map(usize::from).collect())
+                .collect(),
+        )
     }
 }
 
@@ -90,7 +134,7 @@ impl Graph<usize> for AdjacencyMatrix {
         if let Some(edges) = self.0.get_mut(n)
             && let Some(edge) = edges.get_mut(m)
         {
-            if *edge == 1 {
+            if *edge != 0 {
                 return;
             }
             *edge = 1;
@@ -120,53 +164,14 @@ impl Graph<usize> for AdjacencyMatrix {
         }
     }
 
-    fn bipartite(&self) -> bool {
-        let n = self.order();
-        if n == 0 {
-            return true;
-        }
-
-        let mut side = vec![None; n]; // None = uncolored, Some(0/1) = partition
-        let mut queue = std::collections::VecDeque::new();
-
-        for start in 0..n {
-            // skip already colored components
-            if side[start].is_some() {
-                continue;
-            }
-
-            side[start] = Some(0);
-            queue.push_back(start);
-
-            while let Some(u) = queue.pop_front() {
-                let u_side = side[u].unwrap();
-
-                for (v, &is_edge) in self.0[u].iter().enumerate() {
-                    if is_edge == 0 {
-                        continue;
-                    }
-
-                    if side[v].is_none() {
-                        side[v] = Some(1 - u_side);
-                        queue.push_back(v);
-                    } else if side[v] == Some(u_side) {
-                        return false; // adjacent nodes with same color
-                    }
-                }
-            }
-        }
-
-        true
-    }
-
     fn underlying_graph(&self) -> Self {
         let mut matrix: AdjacencyMatrix =
             AdjacencyMatrix(vec![vec![0; self.0.len()]; self.0.len()]);
 
         for (idx_r, row) in self.0.iter().enumerate() {
             for (idx_c, col) in row.iter().enumerate() {
-                if *col == 1 && !matrix.has_edge(idx_c, idx_r) {
-                    matrix.add_undirected_edge(idx_r, idx_c);
+                if *col != 0 && !matrix.has_edge(idx_c, idx_r) {
+                    matrix.add_undirected_weighted_edge(idx_r, idx_c, *col);
                 }
             }
         }
@@ -188,28 +193,6 @@ impl UndirectedGraph<usize> for AdjacencyMatrix {
         size
     }
 
-    fn connected(&self) -> bool {
-        let n = self.order();
-        if n == 0 {
-            return true;
-        }
-
-        let mut visited = vec![false; n];
-        let mut stack = vec![0];
-        visited[0] = true;
-
-        while let Some(u) = stack.pop() {
-            for (v, &is_edge) in self.0[u].iter().enumerate() {
-                if is_edge > 0 && !visited[v] {
-                    visited[v] = true;
-                    stack.push(v);
-                }
-            }
-        }
-
-        visited.into_iter().all(|v| v)
-    }
-
     fn undirected_node_degree(&self, node: usize) -> usize {
         if let Some(row) = self.0.get(node) {
             row.iter().filter(|&&val| val != 0).count()
@@ -219,11 +202,46 @@ impl UndirectedGraph<usize> for AdjacencyMatrix {
     }
 }
 
+impl WeightedGraph<usize, usize> for AdjacencyMatrix {
+    /// Adds a directed edge from `n` to `m` with weight `w`.
+    ///
+    /// Since an empty cell holds 0, adding an edge with weight 0 removes it.
+    fn add_weighted_edge(&mut self, n: usize, m: usize, w: usize) {
+        if let Some(edges) = self.0.get_mut(n)
+            && let Some(edge) = edges.get_mut(m)
+        {
+            *edge = w;
+        }
+    }
+
+    fn edge_weight(&self, n: usize, m: usize) -> Option<usize> {
+        self.0
+            .get(n)
+            .and_then(|row| row.get(m))
+            .copied()
+            .filter(|&w| w != 0)
+    }
+
+    fn weighted_neighbors<'a>(&'a self, n: usize) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        usize: 'a,
+    {
+        self.0
+            .get(n)
+            .into_iter()
+            .flat_map(|row| row.iter().copied().enumerate())
+            .filter(|&(_, w)| w != 0)
+    }
+}
+
 impl GraphIO<usize> for AdjacencyMatrix {}
 
 impl UndirectedGraphIO<usize> for AdjacencyMatrix {}
 
+impl WeightedGraphIO<usize, usize> for AdjacencyMatrix {}
+
 #[cfg(test)]
+#[allow(clippy::single_match)]
 mod tests {
     use std::{
         io::{Error, ErrorKind},
@@ -619,4 +637,47 @@ mod tests {
         assert_eq!(degrees_1, (1, 1)); // in: 0->1, out: 1->2
         assert_eq!(degrees_2, (1, 1)); // in: 1->2, out: 2->0
     }
+
+    #[test]
+    fn new_weighted_digraph() {
+        let result: Result<AdjacencyMatrix, Error> =
+            WeightedGraphIO::import_weighted_from_file(PATH.to_owned() + "DIGRAFO_PONDERADO.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(matrix) = result {
+            assert_eq!(matrix.order(), 6);
+            assert_eq!(matrix.size(), 9);
+            assert_eq!(matrix.edge_weight(2, 5), Some(2));
+            assert_eq!(matrix.edge_weight(5, 2), None);
+        }
+    }
+
+    #[test]
+    fn weighted_edges_on_matrix() {
+        // Graph: 0 -(4)-> 1 -(1)-> 2
+        let mut m = AdjacencyMatrix(vec![vec![0; 3]; 3]);
+        m.add_weighted_edge(0, 1, 4);
+        m.add_edge(1, 2);
+        m.add_edge(0, 1);
+
+        assert_eq!(m.edge_weight(0, 1), Some(4));
+        assert_eq!(m.edge_weight(1, 2), Some(1));
+        assert_eq!(m.weighted_neighbors(0).collect::<Vec<_>>(), vec![(1, 4)]);
+
+        m.add_weighted_edge(0, 1, 0);
+        assert!(!m.has_edge(0, 1));
+    }
+
+    #[test]
+    fn weighted_underlying_graph() {
+        // Graph: 0 -(3)-> 1 <-(7)- 2
+        let m = AdjacencyMatrix(vec![vec![0, 3, 0], vec![0, 0, 0], vec![0, 7, 0]]);
+        let underlying = m.underlying_graph();
+
+        assert_eq!(
+            underlying.0,
+            vec![vec![0, 3, 0], vec![3, 0, 7], vec![0, 7, 0]]
+        );
+    }
 }
@@ -0,0 +1,320 @@
+use crate::Graph;
+use crate::graphs::WeightedAdjacencyList;
+use crate::shortest_paths::NegativeCycle;
+use crate::weighted_graph::{Weight, WeightedGraph};
+
+/// Represents the shortest paths between every pair of nodes of a graph.
+///
+/// It holds a distance matrix and a next-hop matrix, where `next[u][v]` is the node
+/// that follows `u` on a shortest path from `u` to `v`. Both are indexed by node.
+#[derive(Debug, Clone)]
+pub struct AllPairsShortestPaths<W> {
+    distances: Vec<Vec<Option<W>>>,
+    next: Vec<Vec<Option<usize>>>,
+}
+
+impl<W: Weight> AllPairsShortestPaths<W> {
+    /// Returns the distance from `u` to `v`, or `None` if `v` isn't reachable from `u`.
+    pub fn distance(&self, u: usize, v: usize) -> Option<W> {
+        self.distances.get(u)?.get(v).copied().flatten()
+    }
+
+    /// Returns the distance matrix, where `None` marks unreachable pairs.
+    pub fn distances(&self) -> &[Vec<Option<W>>] {
+        &self.distances
+    }
+
+    /// Returns the node that follows `u` on a shortest path from `u` to `v`.
+    pub fn next_hop(&self, u: usize, v: usize) -> Option<usize> {
+        self.next.get(u)?.get(v).copied().flatten()
+    }
+
+    /// Rebuilds a shortest path from `u` to `v`, with both ends included.
+    ///
+    /// Returns `None` if `v` isn't reachable from `u`.
+    pub fn path(&self, mut u: usize, v: usize) -> Option<Vec<usize>> {
+        self.distance(u, v)?;
+
+        let mut path = vec![u];
+        while u != v {
+            u = self.next_hop(u, v)?;
+            path.push(u);
+        }
+        Some(path)
+    }
+}
+
+/// Relaxes every pair `(i, j)` through every intermediate node `k`, the core of
+/// the Floyd-Warshall algorithm.
+fn relax_through_every_node<W: Weight>(
+    distances: &mut [Vec<Option<W>>],
+    next: &mut [Vec<Option<usize>>],
+) {
+    let n = distances.len();
+    for k in 0..n {
+        for i in 0..n {
+            let Some(ik) = distances[i][k] else {
+                continue;
+            };
+            for j in 0..n {
+                if let Some(kj) = distances[k][j]
+                    && distances[i][j].is_none_or(|ij| ik + kj < ij)
+                {
+                    distances[i][j] = Some(ik + kj);
+                    next[i][j] = next[i][k];
+                }
+            }
+        }
+    }
+}
+
+/// Builds the next-hop matrix of the direct edges stored on `distances`.
+fn direct_hops<W>(distances: &[Vec<Option<W>>]) -> Vec<Vec<Option<usize>>> {
+    distances
+        .iter()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(j, w)| w.as_ref().map(|_| j))
+                .collect()
+        })
+        .collect()
+}
+
+/// Runs the Floyd-Warshall algorithm over the dense `edges` rows,
+/// where `edges[u][v]` holds the weight of the edge `(u -> v)`, if any.
+pub(crate) fn floyd_warshall<W: Weight>(
+    edges: Vec<Vec<Option<W>>>,
+) -> Result<AllPairsShortestPaths<W>, NegativeCycle<usize>> {
+    let mut distances = edges.clone();
+    for (i, row) in distances.iter_mut().enumerate() {
+        if row[i].is_none_or(|w| W::zero() < w) {
+            row[i] = Some(W::zero());
+        }
+    }
+
+    let mut next = direct_hops(&distances);
+    relax_through_every_node(&mut distances, &mut next);
+
+    match (0..distances.len()).find(|&i| distances[i][i].is_some_and(|w| w < W::zero())) {
+        Some(node) => Err(negative_cycle_through(&edges, node)),
+        None => Ok(AllPairsShortestPaths { distances, next }),
+    }
+}
+
+/// Computes which nodes can be reached from each node through a path with at least one edge.
+///
+/// It runs the same relaxation as [`floyd_warshall`] with unit weights, but without
+/// the empty path from each node to itself.
+pub(crate) fn transitive_closure(edges: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let mut distances: Vec<Vec<Option<usize>>> = edges
+        .iter()
+        .map(|row| row.iter().map(|&edge| edge.then_some(1)).collect())
+        .collect();
+    let mut next = direct_hops(&distances);
+
+    relax_through_every_node(&mut distances, &mut next);
+
+    distances
+        .iter()
+        .map(|row| row.iter().map(|d| d.is_some()).collect())
+        .collect()
+}
+
+/// Runs Johnson's algorithm over a sparse weighted adjacency list.
+///
+/// The weights are made non-negative with the potentials found by Bellman-Ford from a
+/// virtual node linked to every node, then Dijkstra runs from each node on the reweighted graph.
+pub(crate) fn johnson<W: Weight>(
+    list: &WeightedAdjacencyList<W>,
+) -> Result<AllPairsShortestPaths<W>, NegativeCycle<usize>> {
+    let n = list.order();
+
+    let mut extended = list.clone();
+    extended.add_node(n);
+    for v in 0..n {
+        extended.add_weighted_edge(n, v, W::zero());
+    }
+    let potentials = extended.bellman_ford(n)?;
+    let h = |v: usize| potentials.distance(v).unwrap_or(W::zero());
+
+    let reweighted = WeightedAdjacencyList(
+        list.0
+            .iter()
+            .enumerate()
+            .map(|(u, neighbors)| {
+                neighbors
+                    .iter()
+                    .map(|&(v, w)| (v, w + h(u) - h(v)))
+                    .collect()
+            })
+            .collect(),
+    );
+
+    let mut distances = vec![vec![None; n]; n];
+    let mut next = vec![vec![None; n]; n];
+
+    for u in 0..n {
+        let paths = reweighted.dijkstra(u);
+        for (&v, &d) in paths.distances() {
+            distances[u][v] = Some(d - h(u) + h(v));
+        }
+
+        // The first hop towards `v` is the first hop towards its predecessor,
+        // unless that predecessor is `u` itself.
+        next[u][u] = Some(u);
+        for v in 0..n {
+            let mut chain = Vec::new();
+            let mut current = v;
+            let hop = loop {
+                if let Some(hop) = next[u][current] {
+                    break Some(hop);
+                }
+                chain.push(current);
+                match paths.predecessor(current) {
+                    Some(p) if p == u => break Some(current),
+                    Some(p) => current = p,
+                    None => break None,
+                }
+            };
+            for node in chain {
+                next[u][node] = hop;
+            }
+        }
+    }
+
+    Ok(AllPairsShortestPaths { distances, next })
+}
+
+/// Finds a negative cycle through `node`, which is known to lie on one, on the `edges` rows.
+fn negative_cycle_through<W: Weight>(
+    edges: &[Vec<Option<W>>],
+    node: usize,
+) -> NegativeCycle<usize> {
+    let list = WeightedAdjacencyList(
+        edges
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .filter_map(|(j, w)| w.map(|w| (j, w)))
+                    .collect()
+            })
+            .collect(),
+    );
+
+    list.bellman_ford(node)
+        .expect_err("a negative distance from a node to itself means a negative cycle")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+
+    use super::*;
+    use crate::WeightedGraphIO;
+    use crate::graphs::AdjacencyMatrix;
+
+    static PATH: &str = "examples/data/";
+
+    #[test]
+    fn floyd_warshall_on_adjacency_matrix() {
+        let result: Result<AdjacencyMatrix, Error> =
+            WeightedGraphIO::import_weighted_from_file(PATH.to_owned() + "DIGRAFO_PONDERADO.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(matrix) = result {
+            let paths = matrix.floyd_warshall();
+
+            assert_eq!(paths.distance(0, 4), Some(26));
+            assert_eq!(paths.distance(1, 5), Some(12));
+            assert_eq!(paths.distance(3, 3), Some(0));
+            assert_eq!(paths.distance(5, 0), None);
+            assert_eq!(paths.path(0, 4), Some(vec![0, 2, 3, 4]));
+            assert_eq!(paths.path(2, 2), Some(vec![2]));
+            assert_eq!(paths.path(4, 0), None);
+        }
+    }
+
+    #[test]
+    fn johnson_matches_floyd_warshall() {
+        // Graph: 0 -(4)-> 1 -(2)-> 3 -(1)-> 0
+        //         \\      ^
+        //         (5)   (-3)
+        //           \\  /
+        //            2
+        let list = WeightedAdjacencyList(vec![
+            vec![(1, 4), (2, 5)],
+            vec![(3, 2)],
+            vec![(1, -3)],
+            vec![(0, 1)],
+        ]);
+        let rows: Vec<Vec<Option<i32>>> = (0..4)
+            .map(|u| (0..4).map(|v| list.edge_weight(u, v)).collect())
+            .collect();
+
+        let johnson = list.johnson();
+        let floyd = floyd_warshall(rows);
+
+        assert!(johnson.is_ok() && floyd.is_ok());
+
+        if let (Ok(johnson), Ok(floyd)) = (johnson, floyd) {
+            assert_eq!(johnson.distances(), floyd.distances());
+            assert_eq!(johnson.distance(0, 3), Some(4));
+            assert_eq!(johnson.distance(2, 0), Some(0));
+            for u in 0..4 {
+                for v in 0..4 {
+                    assert_eq!(johnson.path(u, v), floyd.path(u, v));
+                }
+            }
+            assert_eq!(johnson.path(0, 3), Some(vec![0, 2, 1, 3]));
+        }
+    }
+
+    #[test]
+    fn negative_cycle_detection() {
+        // Graph: 0 -(1)-> 1 -(-2)-> 2 -(0)-> 0, 2 -(3)-> 3
+        let list = WeightedAdjacencyList(vec![
+            vec![(1, 1)],
+            vec![(2, -2)],
+            vec![(0, 0), (3, 3)],
+            vec![],
+        ]);
+        let rows: Vec<Vec<Option<i64>>> = (0..4)
+            .map(|u| (0..4).map(|v| list.edge_weight(u, v)).collect())
+            .collect();
+
+        for result in [list.johnson(), floyd_warshall(rows)] {
+            assert!(result.is_err());
+
+            if let Err(NegativeCycle(mut cycle)) = result {
+                cycle.sort();
+                assert_eq!(cycle, vec![0, 1, 2]);
+            }
+        }
+    }
+
+    #[test]
+    fn transitive_closure_of_adjacency_matrix() {
+        // Graph: 0 -> 1 -> 2 -> 1    3
+        let matrix = AdjacencyMatrix(vec![
+            vec![0, 1, 0, 0],
+            vec![0, 0, 1, 0],
+            vec![0, 1, 0, 0],
+            vec![0, 0, 0, 0],
+        ]);
+
+        let closure = matrix.transitive_closure();
+
+        assert_eq!(
+            closure.0,
+            vec![
+                vec![0, 1, 1, 0],
+                vec![0, 1, 1, 0],
+                vec![0, 1, 1, 0],
+                vec![0, 0, 0, 0],
+            ]
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use crate::weighted_graph::Weight;
+
+/// Represents an optimal **assignment** of the rows of a cost matrix to its columns,
+/// where no two rows share a column.
+///
+/// If the matrix isn't square, every row is assigned when there are more columns than rows,
+/// and every column otherwise.
+#[derive(Debug, Clone)]
+pub struct Assignment<W> {
+    pairs: Vec<(usize, usize)>,
+    columns: Vec<Option<usize>>,
+    total: W,
+}
+
+impl<W: Weight> Assignment<W> {
+    /// Finds an assignment of the least total cost with the **Hungarian algorithm**
+    /// (Kuhn-Munkres), on `O(n²m)` time for `n` rows and `m` columns, `n ≤ m`.
+    ///
+    /// `costs[i][j]` is the cost of assigning row `i` to column `j`, the same way
+    /// [`AdjacencyMatrix`](crate::graphs::AdjacencyMatrix) stores its rows.
+    ///
+    /// # Panics
+    /// If the rows don't all have the same length.
+    pub fn minimize(costs: &[Vec<W>]) -> Self {
+        Self::solve(costs, costs.to_vec())
+    }
+
+    /// Finds an assignment of the greatest total cost with the **Hungarian algorithm**.
+    ///
+    /// Each cost is subtracted from the largest one, so that maximising the original costs
+    /// turns into minimising non-negative ones, which also works for unsigned weights.
+    ///
+    /// # Panics
+    /// If the rows don't all have the same length.
+    pub fn maximize(costs: &[Vec<W>]) -> Self {
+        let max = costs
+            .iter()
+            .flatten()
+            .copied()
+            .reduce(|max, c| if c > max { c } else { max })
+            .unwrap_or(W::zero());
+        let flipped = costs
+            .iter()
+            .map(|row| row.iter().map(|&c| max - c).collect())
+            .collect();
+        Self::solve(costs, flipped)
+    }
+
+    /// Returns the assigned `(row, column)` pairs, by increasing row.
+    pub fn pairs(&self) -> &[(usize, usize)] {
+        &self.pairs
+    }
+
+    /// Returns the column assigned to `row`, or `None` if it was left out.
+    pub fn column(&self, row: usize) -> Option<usize> {
+        self.columns.get(row).copied().flatten()
+    }
+
+    /// Returns the total cost of the assignment, on the original costs.
+    pub fn total_cost(&self) -> W {
+        self.total
+    }
+
+    /// Minimises `reduced`, a matrix of the same shape as `costs`, and totals the
+    /// resulting assignment on `costs`.
+    fn solve(costs: &[Vec<W>], reduced: Vec<Vec<W>>) -> Self {
+        let width = costs.first().map_or(0, |row| row.len());
+        assert!(
+            costs.iter().all(|row| row.len() == width),
+            "every row of the cost matrix must have the same length"
+        );
+
+        let pairs = if costs.len() <= width {
+            hungarian(&reduced, width)
+        } else {
+            let transposed: Vec<Vec<W>> = (0..width)
+                .map(|j| reduced.iter().map(|row| row[j]).collect())
+                .collect();
+            let mut pairs: Vec<(usize, usize)> = hungarian(&transposed, costs.len())
+                .into_iter()
+                .map(|(j, i)| (i, j))
+                .collect();
+            pairs.sort_unstable();
+            pairs
+        };
+
+        let mut columns = vec![None; costs.len()];
+        let mut total = W::zero();
+        for &(i, j) in &pairs {
+            columns[i] = Some(j);
+            total = total + costs[i][j];
+        }
+        Self {
+            pairs,
+            columns,
+            total,
+        }
+    }
+}
+
+/// Assigns every row of `costs` to one of its `width` columns at the least total cost,
+/// with `costs.len() <= width`. Returns the `(row, column)` pairs by increasing row.
+///
+/// Rows are added one at a time, growing a shortest augmenting path over the reduced
+/// costs `costs[i][j] - u[i] - v[j]`. Since `u` only grows and `v` only shrinks from zero,
+/// `v` is kept negated so that unsigned weights never underflow.
+fn hungarian<W: Weight>(costs: &[Vec<W>], width: usize) -> Vec<(usize, usize)> {
+    // Rows and columns are numbered from 1, leaving 0 as a virtual column
+    // that holds the row being added.
+    let mut u = vec![W::zero(); costs.len() + 1];
+    let mut v = vec![W::zero(); width + 1];
+    let mut row_of = vec![0; width + 1];
+    let mut way = vec![0; width + 1];
+
+    for i in 1..=costs.len() {
+        row_of[0] = i;
+        let mut j0 = 0;
+        let mut min_slack: Vec<Option<W>> = vec![None; width + 1];
+        let mut used = vec![false; width + 1];
+
+        loop {
+            used[j0] = true;
+            let i0 = row_of[j0];
+            let mut delta: Option<W> = None;
+            let mut j1 = 0;
+
+            for j in 1..=width {
+                if used[j] {
+                    continue;
+                }
+                let slack = costs[i0 - 1][j - 1] + v[j] - u[i0];
+                if min_slack[j].is_none_or(|min| slack < min) {
+                    min_slack[j] = Some(slack);
+                    way[j] = j0;
+                }
+                if let Some(min) = min_slack[j]
+                    && delta.is_none_or(|delta| min < delta)
+                {
+                    delta = Some(min);
+                    j1 = j;
+                }
+            }
+
+            let delta = delta.expect("there are at least as many columns as rows");
+            for j in 0..=width {
+                if used[j] {
+                    u[row_of[j]] = u[row_of[j]] + delta;
+                    v[j] = v[j] + delta;
+                } else if let Some(min) = min_slack[j] {
+                    min_slack[j] = Some(min - delta);
+                }
+            }
+
+            j0 = j1;
+            if row_of[j0] == 0 {
+                break;
+            }
+        }
+
+        while j0 != 0 {
+            let j1 = way[j0];
+            row_of[j0] = row_of[j1];
+            j0 = j1;
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize)> = (1..=width)
+        .filter(|&j| row_of[j] != 0)
+        .map(|j| (row_of[j] - 1, j - 1))
+        .collect();
+    pairs.sort_unstable();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Assignment;
+    use crate::test_rng::XorShift;
+
+    /// Returns the least and the greatest total cost over every assignment, by brute force.
+    fn brute_force(costs: &[Vec<i64>]) -> (i64, i64) {
+        fn search(costs: &[Vec<i64>], row: usize, used: &mut Vec<bool>) -> (i64, i64) {
+            if row == costs.len() {
+                return (0, 0);
+            }
+            let mut best = (i64::MAX, i64::MIN);
+            for j in 0..used.len() {
+                if !used[j] {
+                    used[j] = true;
+                    let (min, max) = search(costs, row + 1, used);
+                    best = (
+                        best.0.min(min + costs[row][j]),
+                        best.1.max(max + costs[row][j]),
+                    );
+                    used[j] = false;
+                }
+            }
+            best
+        }
+        search(costs, 0, &mut vec![false; costs[0].len()])
+    }
+
+    #[test]
+    fn square_assignment() {
+        // Workers on rows, jobs on columns.
+        let costs = vec![
+            vec![9, 2, 7, 8],
+            vec![6, 4, 3, 7],
+            vec![5, 8, 1, 8],
+            vec![7, 6, 9, 4],
+        ];
+
+        let cheapest = Assignment::minimize(&costs);
+
+        assert_eq!(cheapest.pairs(), &[(0, 1), (1, 0), (2, 2), (3, 3)]);
+        assert_eq!(cheapest.total_cost(), 13);
+        assert_eq!(cheapest.column(1), Some(0));
+
+        let dearest = Assignment::maximize(&costs);
+
+        assert_eq!(dearest.total_cost(), 9 + 7 + 8 + 9);
+        assert_eq!(dearest.pairs().len(), 4);
+    }
+
+    #[test]
+    fn rectangular_assignment() {
+        let wide: Vec<Vec<u32>> = vec![vec![4, 1, 3, 7], vec![2, 0, 5, 1]];
+
+        let result = Assignment::minimize(&wide);
+
+        assert_eq!(result.pairs(), &[(0, 1), (1, 3)]);
+        assert_eq!(result.total_cost(), 2);
+
+        // The transpose leaves out two rows instead of two columns.
+        let tall: Vec<Vec<u32>> = (0..4)
+            .map(|j| wide.iter().map(|row| row[j]).collect())
+            .collect();
+
+        let result = Assignment::minimize(&tall);
+
+        assert_eq!(result.pairs(), &[(1, 0), (3, 1)]);
+        assert_eq!(result.column(0), None);
+        assert_eq!(result.total_cost(), 2);
+    }
+
+    #[test]
+    fn negative_costs_against_brute_force() {
+        let mut rng = XorShift::new(0x9e37_79b9_7f4a_7c15);
+
+        for (rows, columns) in [(1, 3), (3, 3), (3, 5), (5, 5), (4, 6)] {
+            let costs: Vec<Vec<i64>> = (0..rows)
+                .map(|_| (0..columns).map(|_| rng.below(41) as i64 - 20).collect())
+                .collect();
+
+            let (min, max) = brute_force(&costs);
+
+            assert_eq!(Assignment::minimize(&costs).total_cost(), min);
+            assert_eq!(Assignment::maximize(&costs).total_cost(), max);
+        }
+    }
+
+    #[test]
+    fn empty_and_float_assignments() {
+        let empty: Vec<Vec<f64>> = vec![];
+        assert!(Assignment::minimize(&empty).pairs().is_empty());
+
+        let costs = vec![vec![0.5, 2.25], vec![1.0, 0.75]];
+        let result = Assignment::minimize(&costs);
+        assert_eq!(result.pairs(), &[(0, 0), (1, 1)]);
+        assert_eq!(result.total_cost(), 1.25);
+    }
+}
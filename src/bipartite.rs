@@ -0,0 +1,223 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::Graph;
+use crate::components::predecessors;
+
+/// Represents a split of the nodes of a bipartite graph into two sides,
+/// such that every edge joins a node of each side.
+#[derive(Debug, Clone)]
+pub struct Bipartition<Node> {
+    left: Vec<Node>,
+    right: Vec<Node>,
+    in_left: HashMap<Node, bool>,
+}
+
+impl<Node: Eq + Hash + Copy> Bipartition<Node> {
+    /// Returns the nodes on the left side, in [`Graph::nodes`] order.
+    pub fn left(&self) -> &[Node] {
+        &self.left
+    }
+
+    /// Returns the nodes on the right side, in [`Graph::nodes`] order.
+    pub fn right(&self) -> &[Node] {
+        &self.right
+    }
+
+    /// Returns `true` if `node` is on the left side, `false` if it's on the right side,
+    /// or `None` if it's not a node of the graph.
+    pub fn is_left(&self, node: Node) -> Option<bool> {
+        self.in_left.get(&node).copied()
+    }
+}
+
+/// A cycle with an odd number of edges, which proves that a graph isn't bipartite.
+///
+/// The nodes are listed in the order the edges of the cycle are followed, and the last node
+/// is adjacent to the first one. A self-loop is a cycle with a single node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OddCycle<Node>(pub Vec<Node>);
+
+/// Colours the nodes of `graph` with two colours by BFS, ignoring the direction of edges.
+///
+/// The first node of each component (on [`Graph::nodes`] order) goes to the left side.
+/// When an edge joins two nodes of the same colour, both lie at the same depth of the
+/// BFS tree, so the tree paths from them up to their closest common ancestor, closed
+/// by that edge, form an odd cycle.
+pub(crate) fn bipartition<Node, G>(graph: &G) -> Result<Bipartition<Node>, OddCycle<Node>>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    let predecessors = predecessors(graph);
+    let mut in_left: HashMap<Node, bool> = HashMap::with_capacity(graph.order());
+    let mut parent: HashMap<Node, Node> = HashMap::with_capacity(graph.order());
+    let mut queue: VecDeque<Node> = VecDeque::new();
+
+    for root in graph.nodes() {
+        if in_left.contains_key(&root) {
+            continue;
+        }
+        in_left.insert(root, true);
+        queue.push_back(root);
+
+        while let Some(u) = queue.pop_front() {
+            let side = in_left[&u];
+            let adjacent = graph
+                .neighbors(u)
+                .chain(predecessors.get(&u).into_iter().flatten().copied());
+
+            for v in adjacent {
+                match in_left.get(&v) {
+                    None => {
+                        in_left.insert(v, !side);
+                        parent.insert(v, u);
+                        queue.push_back(v);
+                    }
+                    Some(&other) if other == side => {
+                        return Err(odd_cycle(&parent, u, v));
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    let (left, right) = graph.nodes().partition(|n| in_left[n]);
+    Ok(Bipartition {
+        left,
+        right,
+        in_left,
+    })
+}
+
+/// Closes the edge `(u, v)` between two nodes at the same depth of the BFS tree
+/// into a cycle through their closest common ancestor.
+fn odd_cycle<Node>(parent: &HashMap<Node, Node>, u: Node, v: Node) -> OddCycle<Node>
+where
+    Node: Eq + Hash + Copy,
+{
+    let (mut a, mut b) = (u, v);
+    let mut from_u = vec![a];
+    let mut from_v = Vec::new();
+
+    while a != b {
+        a = parent[&a];
+        from_u.push(a);
+        from_v.push(b);
+        b = parent[&b];
+    }
+
+    from_u.extend(from_v.into_iter().rev());
+    OddCycle(from_u)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+
+    use super::OddCycle;
+    use crate::graphs::{AdjacencyList, AdjacencyMatrix, IncidenceMatrix};
+    use crate::{Graph, UndirectedGraph, UndirectedGraphIO};
+
+    static PATH: &str = "examples/data/";
+
+    /// Checks that `cycle` has an odd length and is a closed walk on the graph,
+    /// following the edges on any direction.
+    fn is_odd_cycle<G: Graph<usize>>(graph: &G, OddCycle(cycle): &OddCycle<usize>) -> bool {
+        cycle.len() % 2 == 1
+            && cycle
+                .iter()
+                .zip(cycle.iter().cycle().skip(1))
+                .all(|(&n, &m)| graph.has_edge(n, m) || graph.has_edge(m, n))
+    }
+
+    #[test]
+    fn even_cycle_is_bipartite() {
+        // 0 -- 1
+        // |    |    4 -- 5
+        // 3 -- 2
+        let mut list = AdjacencyList(vec![vec![]; 6]);
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)] {
+            list.add_undirected_edge(u, v);
+        }
+
+        let result = list.bipartition();
+
+        assert!(result.is_ok());
+
+        if let Ok(sides) = result {
+            assert_eq!(sides.left(), &[0, 2, 4]);
+            assert_eq!(sides.right(), &[1, 3, 5]);
+            assert_eq!(sides.is_left(5), Some(false));
+            assert_eq!(sides.is_left(6), None);
+        }
+        assert!(list.bipartite());
+    }
+
+    #[test]
+    fn odd_cycle_on_every_representation() {
+        // 0 -- 1 -- 2
+        // |         |
+        // 4 ------- 3
+        let mut list = AdjacencyList(vec![vec![]; 5]);
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)] {
+            list.add_undirected_edge(u, v);
+        }
+        let matrix = AdjacencyMatrix::from_adjacency_list(&list);
+        let incidence = IncidenceMatrix::from_undirected_adjacency_matrix(&matrix);
+
+        for result in [
+            list.bipartition(),
+            matrix.bipartition(),
+            incidence.bipartition(),
+        ] {
+            assert!(result.is_err());
+
+            if let Err(cycle) = result {
+                assert_eq!(cycle.0.len(), 5);
+                assert!(is_odd_cycle(&list, &cycle));
+            }
+        }
+        assert!(!list.bipartite() && !matrix.bipartite() && !incidence.bipartite());
+    }
+
+    #[test]
+    fn directed_triangle_and_self_loop() {
+        // 0 -> 1 -> 2 <- 0
+        let digraph = AdjacencyList(vec![vec![1, 2], vec![2], vec![]]);
+        let result = digraph.bipartition();
+
+        assert!(result.is_err());
+        if let Err(cycle) = result {
+            assert!(is_odd_cycle(&digraph, &cycle));
+        }
+
+        // 0 -- 1 -- 1
+        let looped = AdjacencyList(vec![vec![1], vec![0, 1]]);
+        assert_eq!(looped.bipartition().err(), Some(OddCycle(vec![1])));
+    }
+
+    #[test]
+    fn bipartition_of_graph_file() {
+        let result: Result<AdjacencyList, Error> =
+            UndirectedGraphIO::import_undirected_from_file(PATH.to_owned() + "GRAFO_2.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(list) = result {
+            match list.bipartition() {
+                Ok(sides) => {
+                    assert_eq!(sides.left().len() + sides.right().len(), list.order());
+                    for u in list.nodes() {
+                        assert!(
+                            list.neighbors(u)
+                                .all(|v| sides.is_left(u) != sides.is_left(v))
+                        );
+                    }
+                }
+                Err(cycle) => assert!(is_odd_cycle(&list, &cycle)),
+            }
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{Error, Write};
+
+use crate::graph::all_biconnected_components;
+use crate::graphs::AdjacencyList;
+use crate::{Graph, UndirectedGraph};
+
+/// Represents what a node of a [`BlockCutTree`] stands for.
+///
+/// # Variants
+/// - `Block(Vec<Node>)`: A biconnected component (block), with the nodes it contains.
+/// - `CutVertex(Node)`: An articulation point, shared by all blocks linked to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockCutNode<Node> {
+    Block(Vec<Node>),
+    CutVertex(Node),
+}
+
+/// Represents the **block-cut tree** of an undirected graph.
+///
+/// It has a node for each block and for each articulation point of the graph, and an
+/// undirected edge between a block and every articulation point it contains. Each connected
+/// component of the graph becomes a tree, and an isolated node becomes a block by itself.
+#[derive(Debug, Clone)]
+pub struct BlockCutTree<Node> {
+    tree: AdjacencyList,
+    kinds: Vec<BlockCutNode<Node>>,
+    tree_node_of: HashMap<Node, usize>,
+}
+
+impl<Node: Eq + Hash + Copy> BlockCutTree<Node> {
+    /// Builds the block-cut tree of `graph`.
+    pub(crate) fn new<G: UndirectedGraph<Node>>(graph: &G) -> Self {
+        let mut blocks: Vec<Vec<Node>> = all_biconnected_components(graph)
+            .into_iter()
+            .map(|edges| {
+                let mut nodes: Vec<Node> = Vec::new();
+                for node in edges.into_iter().flat_map(|(u, v)| [u, v]) {
+                    if !nodes.contains(&node) {
+                        nodes.push(node);
+                    }
+                }
+                nodes
+            })
+            .collect();
+
+        let mut blocks_of: HashMap<Node, Vec<usize>> = HashMap::with_capacity(graph.order());
+        for (i, block) in blocks.iter().enumerate() {
+            for &node in block {
+                blocks_of.entry(node).or_default().push(i);
+            }
+        }
+        for node in graph.nodes() {
+            blocks_of.entry(node).or_insert_with(|| {
+                blocks.push(vec![node]);
+                vec![blocks.len() - 1]
+            });
+        }
+
+        let mut tree = AdjacencyList(vec![Vec::new(); blocks.len()]);
+        let mut kinds: Vec<BlockCutNode<Node>> =
+            blocks.into_iter().map(BlockCutNode::Block).collect();
+        let mut tree_node_of: HashMap<Node, usize> = HashMap::with_capacity(graph.order());
+
+        for node in graph.nodes() {
+            match blocks_of[&node].as_slice() {
+                [block] => {
+                    tree_node_of.insert(node, *block);
+                }
+                blocks => {
+                    let cut = kinds.len();
+                    tree.add_node(cut);
+                    kinds.push(BlockCutNode::CutVertex(node));
+                    tree_node_of.insert(node, cut);
+                    for &block in blocks {
+                        tree.add_undirected_edge(block, cut);
+                    }
+                }
+            }
+        }
+
+        Self {
+            tree,
+            kinds,
+            tree_node_of,
+        }
+    }
+
+    /// Returns the tree as an undirected [`AdjacencyList`].
+    pub fn tree(&self) -> &AdjacencyList {
+        &self.tree
+    }
+
+    /// Returns what the tree node `idx` stands for, or `None` if there's no such node.
+    pub fn kind(&self, idx: usize) -> Option<&BlockCutNode<Node>> {
+        self.kinds.get(idx)
+    }
+
+    /// Returns `true` if the tree node `idx` is a block.
+    pub fn is_block(&self, idx: usize) -> bool {
+        matches!(self.kind(idx), Some(BlockCutNode::Block(_)))
+    }
+
+    /// Returns `true` if the tree node `idx` is an articulation point.
+    pub fn is_cut_vertex(&self, idx: usize) -> bool {
+        matches!(self.kind(idx), Some(BlockCutNode::CutVertex(_)))
+    }
+
+    /// Returns the tree node that holds the graph node `node`: its own node if it's
+    /// an articulation point, or else the only block that contains it.
+    pub fn tree_node_of(&self, node: Node) -> Option<usize> {
+        self.tree_node_of.get(&node).copied()
+    }
+
+    /// Returns the nodes whose single failure disconnects `a` from `b`, ordered from `a` to `b`.
+    ///
+    /// They're the articulation points on the tree path between `a` and `b`, other than
+    /// `a` and `b` themselves. Returns `None` if `a` and `b` aren't connected in the first place.
+    pub fn separating_vertices(&self, a: Node, b: Node) -> Option<Vec<Node>> {
+        let path = self
+            .tree
+            .shortest_path(self.tree_node_of(a)?, self.tree_node_of(b)?)?;
+
+        Some(
+            path.into_iter()
+                .filter_map(|idx| match self.kinds[idx] {
+                    BlockCutNode::CutVertex(node) if node != a && node != b => Some(node),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<Node: Eq + Hash + Copy + Display> BlockCutTree<Node> {
+    /// Exports the block-cut tree to a DOT file for visualization.
+    ///
+    /// Blocks are drawn as boxes listing their nodes, and articulation points as circles.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the output DOT file. If it's inside a folder, this folder must exists earlier.
+    pub fn export_to_dot(&self, mut path: String) -> Result<(), Error> {
+        if !path.contains(".dot") {
+            path += ".dot";
+        }
+
+        let mut file: File = File::create(&path)?;
+
+        writeln!(file, "graph G {{")?;
+        writeln!(file, "  rankdir=LR;")?;
+
+        for (idx, kind) in self.kinds.iter().enumerate() {
+            match kind {
+                BlockCutNode::Block(nodes) => {
+                    let labels: Vec<String> = nodes.iter().map(|node| node.to_string()).collect();
+                    writeln!(
+                        file,
+                        " {} [shape=box, label=\"{{{}}}\"]",
+                        idx,
+                        labels.join(", ")
+                    )?;
+                }
+                BlockCutNode::CutVertex(node) => {
+                    writeln!(file, " {} [shape=circle, label=\"{}\"]", idx, node)?;
+                }
+            }
+        }
+
+        for u in self.tree.nodes() {
+            for v in self.tree.neighbors(u) {
+                if u < v {
+                    writeln!(file, " {} -- {} ", u, v)?;
+                }
+            }
+        }
+
+        writeln!(file, " }}")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::BlockCutNode;
+    use crate::graphs::AdjacencyList;
+    use crate::{Graph, UndirectedGraph};
+
+    /// Builds the graph below.
+    ///
+    /// ```text
+    /// 0 -- 1 -- 4 -- 5      7
+    ///    /  \     \ /
+    ///   3 -- 2     6
+    /// ```
+    fn example_graph() -> AdjacencyList {
+        let mut graph = AdjacencyList(vec![vec![]; 8]);
+        for (u, v) in [
+            (0, 1),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (1, 4),
+            (4, 5),
+            (4, 6),
+            (5, 6),
+        ] {
+            graph.add_undirected_edge(u, v);
+        }
+        graph
+    }
+
+    #[test]
+    fn block_cut_tree_structure() {
+        let graph = example_graph();
+
+        let bct = graph.block_cut_tree();
+
+        // Blocks: {0, 1}, {1, 2, 3}, {1, 4}, {4, 5, 6}, {7}. Cut vertices: 1, 4.
+        let blocks = (0..bct.tree().order()).filter(|&i| bct.is_block(i)).count();
+        let cuts = (0..bct.tree().order())
+            .filter(|&i| bct.is_cut_vertex(i))
+            .count();
+
+        assert_eq!(blocks, 5);
+        assert_eq!(cuts, 2);
+        assert_eq!(bct.tree().undirected_size(), 5);
+
+        let one = bct.tree_node_of(1).unwrap_or(usize::MAX);
+        assert_eq!(bct.kind(one), Some(&BlockCutNode::CutVertex(1)));
+        assert_eq!(bct.tree().undirected_node_degree(one), 3);
+
+        let seven = bct.tree_node_of(7).unwrap_or(usize::MAX);
+        assert_eq!(bct.kind(seven), Some(&BlockCutNode::Block(vec![7])));
+    }
+
+    #[test]
+    fn single_failure_queries() {
+        let graph = example_graph();
+        let bct = graph.block_cut_tree();
+
+        assert_eq!(bct.separating_vertices(0, 5), Some(vec![1, 4]));
+        assert_eq!(bct.separating_vertices(2, 3), Some(vec![]));
+        assert_eq!(bct.separating_vertices(1, 6), Some(vec![4]));
+        assert_eq!(bct.separating_vertices(0, 7), None);
+    }
+
+    #[test]
+    fn block_cut_tree_to_dot() {
+        let graph = example_graph();
+        let bct = graph.block_cut_tree();
+        let name = format!("block_cut_tree_{}.dot", std::process::id());
+        let path = std::env::temp_dir().join(name);
+
+        let result = bct.export_to_dot(path.to_string_lossy().into_owned());
+        let content = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+
+        assert!(result.is_ok());
+
+        let content = content.expect("the DOT file was written");
+        assert_eq!(content.matches("shape=box").count(), 5);
+        assert!(content.contains("shape=box, label=\"{7}\""));
+        assert!(content.contains("shape=circle, label=\"1\""));
+        assert_eq!(content.matches(" -- ").count(), 5);
+    }
+}
@@ -0,0 +1,938 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Div;
+
+use crate::matching::Matching;
+use crate::weighted_graph::{Weight, WeightedGraph};
+use crate::{Graph, UndirectedGraph};
+
+/// Numbers the nodes of `graph` by their position on [`Graph::nodes`], and lists each
+/// undirected edge once as `(i, j)` with `i < j`. Self-loops are left out, as they
+/// never belong to a matching.
+fn indexed_edges<Node, G, T>(
+    graph: &G,
+    edges_of: impl Fn(Node) -> Vec<(Node, T)>,
+) -> (Vec<Node>, Vec<(usize, usize, T)>)
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    let nodes: Vec<Node> = graph.nodes().collect();
+    let index: HashMap<Node, usize> = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+
+    let mut edges = Vec::new();
+    for (i, &n) in nodes.iter().enumerate() {
+        for (m, data) in edges_of(n) {
+            let j = index[&m];
+            if i < j {
+                edges.push((i, j, data));
+            }
+        }
+    }
+    (nodes, edges)
+}
+
+/// Turns the mate of each node index into a [`Matching`] over the nodes.
+fn into_matching<Node>(nodes: &[Node], mate: &[Option<usize>]) -> Matching<Node>
+where
+    Node: Eq + Hash + Copy,
+{
+    let edges = mate
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &m)| m.filter(|&j| i < j).map(|j| (nodes[i], nodes[j])))
+        .collect();
+    Matching::new(edges)
+}
+
+/// The state of Edmonds' blossom algorithm for maximum cardinality matching, over `0..n`.
+///
+/// Each search grows an alternating tree by BFS from a single free node. Odd cycles
+/// (blossoms) are contracted by pointing every node on them to a common `base`.
+struct CardinalityBlossom {
+    adjacent: Vec<Vec<usize>>,
+    mate: Vec<Option<usize>>,
+    parent: Vec<Option<usize>>,
+    base: Vec<usize>,
+    used: Vec<bool>,
+    blossom: Vec<bool>,
+}
+
+impl CardinalityBlossom {
+    fn new(adjacent: Vec<Vec<usize>>) -> Self {
+        let n = adjacent.len();
+        Self {
+            adjacent,
+            mate: vec![None; n],
+            parent: vec![None; n],
+            base: (0..n).collect(),
+            used: vec![false; n],
+            blossom: vec![false; n],
+        }
+    }
+
+    /// Returns the base of the closest common ancestor of `a` and `b` on the tree.
+    fn common_base(&self, mut a: usize, mut b: usize) -> usize {
+        let mut on_path = vec![false; self.adjacent.len()];
+        loop {
+            a = self.base[a];
+            on_path[a] = true;
+            match self.mate[a] {
+                Some(m) => a = self.parent[m].expect("a matched tree node has a parent"),
+                None => break,
+            }
+        }
+        loop {
+            b = self.base[b];
+            if on_path[b] {
+                return b;
+            }
+            let m = self.mate[b].expect("the root is on the path of a");
+            b = self.parent[m].expect("a matched tree node has a parent");
+        }
+    }
+
+    /// Marks the blossoms on the tree path from `v` up to `base`, pointing the parents
+    /// along it towards `child`, so the blossom can be crossed on both directions.
+    fn mark_path(&mut self, mut v: usize, base: usize, mut child: usize) {
+        while self.base[v] != base {
+            let m = self.mate[v].expect("a node under a blossom base is matched");
+            self.blossom[self.base[v]] = true;
+            self.blossom[self.base[m]] = true;
+            self.parent[v] = Some(child);
+            child = m;
+            v = self.parent[m].expect("a matched tree node has a parent");
+        }
+    }
+
+    /// Searches an augmenting path from the free node `root`,
+    /// returning its other end if there's one.
+    fn find_path(&mut self, root: usize) -> Option<usize> {
+        let n = self.adjacent.len();
+        self.used.fill(false);
+        self.parent.fill(None);
+        for (i, base) in self.base.iter_mut().enumerate() {
+            *base = i;
+        }
+
+        self.used[root] = true;
+        let mut queue = VecDeque::from([root]);
+
+        while let Some(v) = queue.pop_front() {
+            for k in 0..self.adjacent[v].len() {
+                let to = self.adjacent[v][k];
+                if self.base[v] == self.base[to] || self.mate[v] == Some(to) {
+                    continue;
+                }
+
+                if to == root || self.mate[to].is_some_and(|m| self.parent[m].is_some()) {
+                    let base = self.common_base(v, to);
+                    self.blossom.fill(false);
+                    self.mark_path(v, base, to);
+                    self.mark_path(to, base, v);
+                    for i in 0..n {
+                        if self.blossom[self.base[i]] {
+                            self.base[i] = base;
+                            if !self.used[i] {
+                                self.used[i] = true;
+                                queue.push_back(i);
+                            }
+                        }
+                    }
+                } else if self.parent[to].is_none() {
+                    self.parent[to] = Some(v);
+                    match self.mate[to] {
+                        None => return Some(to),
+                        Some(m) => {
+                            self.used[m] = true;
+                            queue.push_back(m);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Flips the augmenting path that ends at `v`.
+    fn augment(&mut self, mut v: usize) {
+        loop {
+            let pv = self.parent[v].expect("every node on the path has a parent");
+            let next = self.mate[pv];
+            self.mate[v] = Some(pv);
+            self.mate[pv] = Some(v);
+            match next {
+                Some(ppv) => v = ppv,
+                None => break,
+            }
+        }
+    }
+}
+
+/// Computes a maximum cardinality matching of `graph` with **Edmonds' blossom algorithm**.
+pub(crate) fn maximum_matching<Node, G>(graph: &G) -> Matching<Node>
+where
+    Node: Eq + Hash + Copy,
+    G: UndirectedGraph<Node>,
+{
+    let (nodes, edges) = indexed_edges(graph, |n| graph.neighbors(n).map(|m| (m, ())).collect());
+
+    let mut adjacent = vec![Vec::new(); nodes.len()];
+    for &(i, j, _) in &edges {
+        adjacent[i].push(j);
+        adjacent[j].push(i);
+    }
+
+    let mut search = CardinalityBlossom::new(adjacent);
+    for root in 0..nodes.len() {
+        if search.mate[root].is_none()
+            && let Some(end) = search.find_path(root)
+        {
+            search.augment(end);
+        }
+    }
+
+    into_matching(&nodes, &search.mate)
+}
+
+/// The state of the primal-dual blossom algorithm for maximum weight matching,
+/// following the formulation by Galil ("Efficient algorithms for finding maximum
+/// matching in graphs", 1986).
+///
+/// The vertices are `0..n` and the blossoms `n..2n`. Each edge `k` has two endpoints,
+/// `2k` and `2k + 1`, and a vertex is matched through the endpoint of its mate.
+/// Labels are `1` for S (outer) and `2` for T (inner) blossoms, and `0` for free ones.
+/// Every weight is doubled on the slacks, so integer weights keep integer duals.
+struct WeightedBlossom<W> {
+    n: usize,
+    edges: Vec<(usize, usize, W)>,
+    endpoint: Vec<usize>,
+    neighbor_ends: Vec<Vec<usize>>,
+    mate: Vec<Option<usize>>,
+    label: Vec<u8>,
+    label_end: Vec<Option<usize>>,
+    in_blossom: Vec<usize>,
+    parent: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    base: Vec<Option<usize>>,
+    child_ends: Vec<Vec<usize>>,
+    best_edge: Vec<Option<usize>>,
+    best_edges: Vec<Option<Vec<usize>>>,
+    unused: Vec<usize>,
+    dual: Vec<W>,
+    allowed: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+impl<W: Weight + Div<Output = W>> WeightedBlossom<W> {
+    fn new(n: usize, edges: Vec<(usize, usize, W)>) -> Self {
+        let max_weight = edges
+            .iter()
+            .map(|&(_, _, w)| w)
+            .fold(W::zero(), |max, w| if w > max { w } else { max });
+
+        let endpoint = edges.iter().flat_map(|&(i, j, _)| [i, j]).collect();
+        let mut neighbor_ends = vec![Vec::new(); n];
+        for (k, &(i, j, _)) in edges.iter().enumerate() {
+            neighbor_ends[i].push(2 * k + 1);
+            neighbor_ends[j].push(2 * k);
+        }
+
+        Self {
+            n,
+            endpoint,
+            neighbor_ends,
+            mate: vec![None; n],
+            label: vec![0; 2 * n],
+            label_end: vec![None; 2 * n],
+            in_blossom: (0..n).collect(),
+            parent: vec![None; 2 * n],
+            children: vec![Vec::new(); 2 * n],
+            base: (0..n).map(Some).chain((0..n).map(|_| None)).collect(),
+            child_ends: vec![Vec::new(); 2 * n],
+            best_edge: vec![None; 2 * n],
+            best_edges: vec![None; 2 * n],
+            unused: (n..2 * n).collect(),
+            dual: (0..2 * n)
+                .map(|b| if b < n { max_weight } else { W::zero() })
+                .collect(),
+            allowed: vec![false; edges.len()],
+            queue: Vec::new(),
+            edges,
+        }
+    }
+
+    /// Returns twice the slack of edge `k` under the current duals.
+    fn slack(&self, k: usize) -> W {
+        let (i, j, w) = self.edges[k];
+        self.dual[i] + self.dual[j] - (w + w)
+    }
+
+    /// Returns `true` if `k` is a better edge than the current `best`.
+    fn improves(&self, k: usize, best: Option<usize>) -> bool {
+        best.is_none_or(|best| self.slack(k) < self.slack(best))
+    }
+
+    /// Returns the vertices inside the blossom `b`, which may be a single vertex.
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        if b < self.n {
+            return vec![b];
+        }
+        self.children[b]
+            .iter()
+            .flat_map(|&child| self.leaves(child))
+            .collect()
+    }
+
+    /// Labels the top-level blossom of `w` with `t`, reached through the endpoint `p`.
+    /// A T-blossom also labels its mate as an S-blossom.
+    fn assign_label(&mut self, w: usize, t: u8, p: Option<usize>) {
+        let b = self.in_blossom[w];
+        self.label[w] = t;
+        self.label[b] = t;
+        self.label_end[w] = p;
+        self.label_end[b] = p;
+        self.best_edge[w] = None;
+        self.best_edge[b] = None;
+
+        if t == 1 {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else {
+            let base = self.base[b].expect("a used blossom has a base");
+            let m = self.mate[base].expect("the base of a T-blossom is matched");
+            self.assign_label(self.endpoint[m], 1, Some(m ^ 1));
+        }
+    }
+
+    /// Traces back from `v` and `w` to find either a new blossom, returning its base,
+    /// or an augmenting path, returning `None`.
+    fn scan_blossom(&mut self, v: usize, w: usize) -> Option<usize> {
+        let mut path = Vec::new();
+        let mut base = None;
+        let (mut v, mut w) = (Some(v), Some(w));
+
+        while let Some(current) = v {
+            let b = self.in_blossom[current];
+            if self.label[b] & 4 != 0 {
+                base = self.base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = 5;
+
+            v = self.label_end[b].map(|p| {
+                let t = self.in_blossom[self.endpoint[p]];
+                self.endpoint[self.label_end[t].expect("a T-blossom has a label end")]
+            });
+            if w.is_some() {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+
+        for b in path {
+            self.label[b] = 1;
+        }
+        base
+    }
+
+    /// Builds a new blossom with base `base`, closed by the edge `k` between two S-blossoms.
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (v, w, _) = self.edges[k];
+        let bb = self.in_blossom[base];
+        let mut bv = self.in_blossom[v];
+        let mut bw = self.in_blossom[w];
+        let b = self
+            .unused
+            .pop()
+            .expect("there's at most one blossom per vertex");
+
+        self.base[b] = Some(base);
+        self.parent[b] = None;
+        self.parent[bb] = Some(b);
+
+        let mut path = Vec::new();
+        let mut ends = Vec::new();
+        while bv != bb {
+            self.parent[bv] = Some(b);
+            path.push(bv);
+            let p = self.label_end[bv].expect("an S-blossom under the base has a label end");
+            ends.push(p);
+            bv = self.in_blossom[self.endpoint[p]];
+        }
+        path.push(bb);
+        path.reverse();
+        ends.reverse();
+        ends.push(2 * k);
+        while bw != bb {
+            self.parent[bw] = Some(b);
+            path.push(bw);
+            let p = self.label_end[bw].expect("an S-blossom under the base has a label end");
+            ends.push(p ^ 1);
+            bw = self.in_blossom[self.endpoint[p]];
+        }
+
+        self.label[b] = 1;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = W::zero();
+
+        for leaf in self.leaves_of(&path) {
+            if self.label[self.in_blossom[leaf]] == 2 {
+                self.queue.push(leaf);
+            }
+            self.in_blossom[leaf] = b;
+        }
+
+        let mut best_to: Vec<Option<usize>> = vec![None; 2 * self.n];
+        for &child in &path {
+            let candidates: Vec<usize> = match self.best_edges[child].take() {
+                Some(edges) => edges,
+                None => self
+                    .leaves(child)
+                    .into_iter()
+                    .flat_map(|leaf| self.neighbor_ends[leaf].iter().map(|&p| p / 2))
+                    .collect(),
+            };
+            for e in candidates {
+                let (i, j, _) = self.edges[e];
+                let far = if self.in_blossom[j] == b { i } else { j };
+                let bj = self.in_blossom[far];
+                if bj != b && self.label[bj] == 1 && self.improves(e, best_to[bj]) {
+                    best_to[bj] = Some(e);
+                }
+            }
+            self.best_edge[child] = None;
+        }
+
+        let best: Vec<usize> = best_to.into_iter().flatten().collect();
+        self.best_edge[b] = None;
+        for &e in &best {
+            if self.improves(e, self.best_edge[b]) {
+                self.best_edge[b] = Some(e);
+            }
+        }
+        self.best_edges[b] = Some(best);
+        self.children[b] = path;
+        self.child_ends[b] = ends;
+    }
+
+    /// Returns the vertices inside every blossom of `blossoms`.
+    fn leaves_of(&self, blossoms: &[usize]) -> Vec<usize> {
+        blossoms.iter().flat_map(|&b| self.leaves(b)).collect()
+    }
+
+    /// Expands the blossom `b` into its children. At the end of a stage, children
+    /// without dual are expanded too; otherwise, a T-blossom relabels the children
+    /// on the even path through it.
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        for child in self.children[b].clone() {
+            self.parent[child] = None;
+            if child < self.n {
+                self.in_blossom[child] = child;
+            } else if end_stage && self.dual[child] == W::zero() {
+                self.expand_blossom(child, end_stage);
+            } else {
+                for leaf in self.leaves(child) {
+                    self.in_blossom[leaf] = child;
+                }
+            }
+        }
+
+        if !end_stage && self.label[b] == 2 {
+            let entry_end = self.label_end[b].expect("a T-blossom has a label end");
+            let entry_child = self.in_blossom[self.endpoint[entry_end ^ 1]];
+            let len = self.children[b].len() as isize;
+            let mut j = self.children[b]
+                .iter()
+                .position(|&child| child == entry_child)
+                .expect("the entry child belongs to the blossom") as isize;
+            let (step, trick): (isize, usize) = if j & 1 == 1 {
+                j -= len;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+            let at = |j: isize| j.rem_euclid(len) as usize;
+
+            let mut p = entry_end;
+            while j != 0 {
+                let q = self.child_ends[b][at(j - trick as isize)];
+                self.label[self.endpoint[p ^ 1]] = 0;
+                self.label[self.endpoint[q ^ trick ^ 1]] = 0;
+                self.assign_label(self.endpoint[p ^ 1], 2, Some(p));
+                self.allowed[q / 2] = true;
+                j += step;
+                p = self.child_ends[b][at(j - trick as isize)] ^ trick;
+                self.allowed[p / 2] = true;
+                j += step;
+            }
+
+            let bv = self.children[b][at(j)];
+            self.label[self.endpoint[p ^ 1]] = 2;
+            self.label[bv] = 2;
+            self.label_end[self.endpoint[p ^ 1]] = Some(p);
+            self.label_end[bv] = Some(p);
+            self.best_edge[bv] = None;
+            j += step;
+
+            while self.children[b][at(j)] != entry_child {
+                let bv = self.children[b][at(j)];
+                if self.label[bv] == 1 {
+                    j += step;
+                    continue;
+                }
+                if let Some(v) = self.leaves(bv).into_iter().find(|&v| self.label[v] != 0) {
+                    self.label[v] = 0;
+                    let base = self.base[bv].expect("a used blossom has a base");
+                    let m = self.mate[base].expect("the base of a T-blossom is matched");
+                    self.label[self.endpoint[m]] = 0;
+                    self.assign_label(v, 2, self.label_end[v]);
+                }
+                j += step;
+            }
+        }
+
+        self.label[b] = 0;
+        self.label_end[b] = None;
+        self.children[b] = Vec::new();
+        self.child_ends[b] = Vec::new();
+        self.base[b] = None;
+        self.best_edges[b] = None;
+        self.best_edge[b] = None;
+        self.unused.push(b);
+    }
+
+    /// Swaps the matched and unmatched edges on the even path inside the blossom `b`
+    /// from vertex `v` to the base, so that `v` becomes its new base.
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.parent[t] != Some(b) {
+            t = self.parent[t].expect("v lies inside b");
+        }
+        if t >= self.n {
+            self.augment_blossom(t, v);
+        }
+
+        let len = self.children[b].len() as isize;
+        let i = self.children[b]
+            .iter()
+            .position(|&child| child == t)
+            .expect("t is a child of b");
+        let mut j = i as isize;
+        let (step, trick): (isize, usize) = if j & 1 == 1 {
+            j -= len;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+        let at = |j: isize| j.rem_euclid(len) as usize;
+
+        while j != 0 {
+            j += step;
+            let t = self.children[b][at(j)];
+            let p = self.child_ends[b][at(j - trick as isize)] ^ trick;
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p]);
+            }
+            j += step;
+            let t = self.children[b][at(j)];
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p ^ 1]);
+            }
+            self.mate[self.endpoint[p]] = Some(p ^ 1);
+            self.mate[self.endpoint[p ^ 1]] = Some(p);
+        }
+
+        self.children[b].rotate_left(i);
+        self.child_ends[b].rotate_left(i);
+        self.base[b] = self.base[self.children[b][0]];
+    }
+
+    /// Augments the matching along the path through the edge `k` between two S-blossoms.
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.in_blossom[s];
+                if bs >= self.n {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = Some(p);
+
+                let Some(end) = self.label_end[bs] else {
+                    break;
+                };
+                let t = self.endpoint[end];
+                let bt = self.in_blossom[t];
+                let bt_end = self.label_end[bt].expect("a T-blossom has a label end");
+                s = self.endpoint[bt_end];
+                let j = self.endpoint[bt_end ^ 1];
+                if bt >= self.n {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = Some(bt_end);
+                p = bt_end ^ 1;
+            }
+        }
+    }
+
+    /// Scans the S-vertices on the queue, growing the trees, and returns `true`
+    /// once the matching was augmented.
+    fn scan_queue(&mut self) -> bool {
+        while let Some(v) = self.queue.pop() {
+            for idx in 0..self.neighbor_ends[v].len() {
+                let p = self.neighbor_ends[v][idx];
+                let k = p / 2;
+                let w = self.endpoint[p];
+                if self.in_blossom[v] == self.in_blossom[w] {
+                    continue;
+                }
+
+                if !self.allowed[k] && self.slack(k) <= W::zero() {
+                    self.allowed[k] = true;
+                }
+
+                let bw = self.in_blossom[w];
+                if self.allowed[k] {
+                    if self.label[bw] == 0 {
+                        self.assign_label(w, 2, Some(p ^ 1));
+                    } else if self.label[bw] == 1 {
+                        match self.scan_blossom(v, w) {
+                            Some(base) => self.add_blossom(base, k),
+                            None => {
+                                self.augment_matching(k);
+                                return true;
+                            }
+                        }
+                    } else if self.label[w] == 0 {
+                        self.label[w] = 2;
+                        self.label_end[w] = Some(p ^ 1);
+                    }
+                } else if self.label[bw] == 1 {
+                    let b = self.in_blossom[v];
+                    if self.improves(k, self.best_edge[b]) {
+                        self.best_edge[b] = Some(k);
+                    }
+                } else if self.label[w] == 0 && self.improves(k, self.best_edge[w]) {
+                    self.best_edge[w] = Some(k);
+                }
+            }
+        }
+        false
+    }
+
+    /// Changes the duals by the largest amount that keeps them feasible, and applies
+    /// what it made possible. Returns `false` when the optimum was reached.
+    fn update_duals(&mut self) -> bool {
+        let n = self.n;
+        let two = W::one() + W::one();
+
+        // A vertex dual reaches zero.
+        let mut delta = self.dual[..n]
+            .iter()
+            .copied()
+            .reduce(|min, d| if d < min { d } else { min })
+            .unwrap_or(W::zero());
+        let mut kind = 1;
+        let mut delta_edge = None;
+        let mut delta_blossom = None;
+
+        // An edge from a free vertex to an S-blossom becomes tight.
+        for v in 0..n {
+            if self.label[self.in_blossom[v]] == 0
+                && let Some(k) = self.best_edge[v]
+            {
+                let d = self.slack(k);
+                if d < delta {
+                    (delta, kind, delta_edge) = (d, 2, Some(k));
+                }
+            }
+        }
+
+        // An edge between two S-blossoms becomes tight.
+        for b in 0..2 * n {
+            if self.parent[b].is_none()
+                && self.label[b] == 1
+                && let Some(k) = self.best_edge[b]
+            {
+                let d = self.slack(k) / two;
+                if d < delta {
+                    (delta, kind, delta_edge) = (d, 3, Some(k));
+                }
+            }
+        }
+
+        // A T-blossom dual reaches zero.
+        for b in n..2 * n {
+            if self.base[b].is_some()
+                && self.parent[b].is_none()
+                && self.label[b] == 2
+                && self.dual[b] < delta
+            {
+                (delta, kind, delta_blossom) = (self.dual[b], 4, Some(b));
+            }
+        }
+
+        for v in 0..n {
+            match self.label[self.in_blossom[v]] {
+                1 => self.dual[v] = self.dual[v] - delta,
+                2 => self.dual[v] = self.dual[v] + delta,
+                _ => {}
+            }
+        }
+        for b in n..2 * n {
+            if self.base[b].is_some() && self.parent[b].is_none() {
+                match self.label[b] {
+                    1 => self.dual[b] = self.dual[b] + delta,
+                    2 => self.dual[b] = self.dual[b] - delta,
+                    _ => {}
+                }
+            }
+        }
+
+        match (kind, delta_edge, delta_blossom) {
+            (2, Some(k), _) => {
+                self.allowed[k] = true;
+                let (i, j, _) = self.edges[k];
+                let s = if self.label[self.in_blossom[i]] == 0 {
+                    j
+                } else {
+                    i
+                };
+                self.queue.push(s);
+                true
+            }
+            (3, Some(k), _) => {
+                self.allowed[k] = true;
+                self.queue.push(self.edges[k].0);
+                true
+            }
+            (4, _, Some(b)) => {
+                self.expand_blossom(b, false);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Runs one stage per augmentation, until no augmenting path improves the weight.
+    fn run(&mut self) {
+        let n = self.n;
+        for _ in 0..n {
+            self.label.fill(0);
+            self.best_edge.fill(None);
+            for edges in &mut self.best_edges[n..] {
+                *edges = None;
+            }
+            self.allowed.fill(false);
+            self.queue.clear();
+
+            for v in 0..n {
+                if self.mate[v].is_none() && self.label[self.in_blossom[v]] == 0 {
+                    self.assign_label(v, 1, None);
+                }
+            }
+
+            let mut augmented = false;
+            loop {
+                if self.scan_queue() {
+                    augmented = true;
+                    break;
+                }
+                if !self.update_duals() {
+                    break;
+                }
+            }
+
+            if !augmented {
+                break;
+            }
+
+            for b in n..2 * n {
+                if self.parent[b].is_none()
+                    && self.base[b].is_some()
+                    && self.label[b] == 1
+                    && self.dual[b] == W::zero()
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+    }
+
+    /// Returns the vertex matched to each vertex.
+    fn mates(&self) -> Vec<Option<usize>> {
+        self.mate
+            .iter()
+            .map(|&p| p.map(|p| self.endpoint[p]))
+            .collect()
+    }
+}
+
+/// Computes a maximum weight matching of `graph` with the primal-dual blossom algorithm,
+/// in `O(n³)` time. Edges without a positive weight are never matched.
+pub(crate) fn maximum_weight_matching<Node, W, G>(graph: &G) -> Matching<Node>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight + Div<Output = W>,
+    G: WeightedGraph<Node, W> + UndirectedGraph<Node>,
+{
+    let (nodes, edges) = indexed_edges(graph, |n| {
+        graph
+            .weighted_neighbors(n)
+            .filter(|&(_, w)| w > W::zero())
+            .collect()
+    });
+
+    let mut search = WeightedBlossom::new(nodes.len(), edges);
+    search.run();
+
+    into_matching(&nodes, &search.mates())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphs::{AdjacencyList, WeightedAdjacencyList};
+    use crate::matching::Matching;
+    use crate::test_rng::XorShift;
+    use crate::{Graph, UndirectedGraph, WeightedGraph};
+
+    /// Returns the largest total of `value` over sets of disjoint edges, by brute force.
+    fn best_matching(n: usize, edges: &[(usize, usize, i64)], value: fn(i64) -> i64) -> i64 {
+        fn search(
+            used: &mut Vec<bool>,
+            edges: &[(usize, usize, i64)],
+            value: fn(i64) -> i64,
+        ) -> i64 {
+            let Some((&(u, v, w), rest)) = edges.split_first() else {
+                return 0;
+            };
+            let mut best = search(used, rest, value);
+            if !used[u] && !used[v] {
+                used[u] = true;
+                used[v] = true;
+                best = best.max(value(w) + search(used, rest, value));
+                used[u] = false;
+                used[v] = false;
+            }
+            best
+        }
+        search(&mut vec![false; n], edges, value)
+    }
+
+    /// Checks that `matching` only has edges of `graph`, without common nodes.
+    fn is_matching<G: Graph<usize>>(graph: &G, matching: &Matching<usize>) -> bool {
+        let mut seen = vec![false; graph.order()];
+        matching.edges().iter().all(|&(u, v)| {
+            let fresh = !seen[u] && !seen[v];
+            seen[u] = true;
+            seen[v] = true;
+            fresh && graph.has_edge(u, v)
+        })
+    }
+
+    #[test]
+    fn maximum_matching_with_blossoms() {
+        // A 5-cycle with a pendant path on 0 and a pendant node on 2:
+        // 5 -- 0 -- 1 -- 2 -- 6 -- 7
+        //      |         |
+        //      4 ------- 3
+        let mut graph = AdjacencyList(vec![vec![]; 8]);
+        for (u, v) in [
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 0),
+            (0, 5),
+            (2, 6),
+            (6, 7),
+        ] {
+            graph.add_undirected_edge(u, v);
+        }
+
+        let matching = graph.maximum_matching();
+
+        assert_eq!(matching.len(), 4);
+        assert!(is_matching(&graph, &matching));
+        assert_eq!(matching.mate(5), Some(0));
+        assert_eq!(matching.mate(7), Some(6));
+    }
+
+    #[test]
+    fn maximum_matching_of_petersen_graph() {
+        let mut graph = AdjacencyList(vec![vec![]; 10]);
+        for i in 0..5 {
+            graph.add_undirected_edge(i, (i + 1) % 5);
+            graph.add_undirected_edge(i, i + 5);
+            graph.add_undirected_edge(i + 5, (i + 2) % 5 + 5);
+        }
+
+        let matching = graph.maximum_matching();
+
+        assert_eq!(matching.len(), 5);
+        assert!(is_matching(&graph, &matching));
+    }
+
+    #[test]
+    fn maximum_weight_matching_prefers_weight() {
+        // 0 -(5)- 1 -(8)- 2 -(5)- 3, where the middle edge alone weighs less than both ends.
+        let mut graph: WeightedAdjacencyList<i64> = WeightedAdjacencyList(vec![vec![]; 4]);
+        graph.add_undirected_weighted_edge(0, 1, 5);
+        graph.add_undirected_weighted_edge(1, 2, 8);
+        graph.add_undirected_weighted_edge(2, 3, 5);
+
+        let matching = graph.maximum_weight_matching();
+
+        assert_eq!(matching.edges(), &[(0, 1), (2, 3)]);
+
+        graph.add_undirected_weighted_edge(1, 2, 11);
+        assert_eq!(graph.maximum_weight_matching().edges(), &[(1, 2)]);
+    }
+
+    #[test]
+    fn matchings_against_brute_force() {
+        // Dense graphs on 7 nodes, so most of them hold odd cycles to shrink into blossoms.
+        let mut rng = XorShift::new(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..12 {
+            let mut graph: WeightedAdjacencyList<i64> = WeightedAdjacencyList(vec![vec![]; 7]);
+            let mut edges = Vec::new();
+            for u in 0..7 {
+                for v in u + 1..7 {
+                    if rng.below(100) < 45 {
+                        let w = rng.below(20) as i64 + 1;
+                        graph.add_undirected_weighted_edge(u, v, w);
+                        edges.push((u, v, w));
+                    }
+                }
+            }
+
+            let matching = graph.maximum_matching();
+            let weighted = graph.maximum_weight_matching();
+            let weight: i64 = weighted
+                .edges()
+                .iter()
+                .filter_map(|&(u, v)| graph.edge_weight(u, v))
+                .sum();
+
+            assert!(is_matching(&graph, &matching) && is_matching(&graph, &weighted));
+            assert_eq!(matching.len() as i64, best_matching(7, &edges, |_| 1));
+            assert_eq!(weight, best_matching(7, &edges, |w| w));
+        }
+    }
+
+    #[test]
+    fn maximum_weight_matching_with_float_weights() {
+        // Triangle 0, 1, 2 with a pendant 3 on 2.
+        let mut graph: WeightedAdjacencyList<f64> = WeightedAdjacencyList(vec![vec![]; 4]);
+        graph.add_undirected_weighted_edge(0, 1, 2.5);
+        graph.add_undirected_weighted_edge(1, 2, 3.0);
+        graph.add_undirected_weighted_edge(2, 0, 1.0);
+        graph.add_undirected_weighted_edge(2, 3, 0.75);
+
+        let matching = graph.maximum_weight_matching();
+
+        assert_eq!(matching.edges(), &[(0, 1), (2, 3)]);
+    }
+}
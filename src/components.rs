@@ -0,0 +1,279 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::iter;
+
+use crate::{Graph, UnionFind};
+
+/// Represents a labelling of the nodes of a graph by connected component.
+///
+/// Components are numbered from `0` in the order their first node
+/// appears on [`Graph::nodes`].
+#[derive(Debug, Clone)]
+pub struct Components<Node> {
+    component_of: HashMap<Node, usize>,
+    sizes: Vec<usize>,
+}
+
+impl<Node: Eq + Hash + Copy> Components<Node> {
+    /// Labels the nodes yielded by `nodes`, walking from each node to the ones
+    /// yielded by `adjacent`. Runs in linear time on the number of nodes and adjacencies.
+    fn label<I>(nodes: impl Iterator<Item = Node>, adjacent: impl Fn(Node) -> I) -> Self
+    where
+        I: Iterator<Item = Node>,
+    {
+        let mut component_of: HashMap<Node, usize> = HashMap::new();
+        let mut sizes: Vec<usize> = Vec::new();
+        let mut queue: VecDeque<Node> = VecDeque::new();
+
+        for root in nodes {
+            if component_of.contains_key(&root) {
+                continue;
+            }
+
+            let id = sizes.len();
+            let mut size = 0;
+            component_of.insert(root, id);
+            queue.push_back(root);
+
+            while let Some(node) = queue.pop_front() {
+                size += 1;
+                for next in adjacent(node) {
+                    if let Entry::Vacant(entry) = component_of.entry(next) {
+                        entry.insert(id);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+
+        Self {
+            component_of,
+            sizes,
+        }
+    }
+
+    /// Labels the connected components of an undirected graph.
+    pub(crate) fn connected<G: Graph<Node>>(graph: &G) -> Self {
+        Self::label(graph.nodes(), |node| graph.neighbors(node))
+    }
+
+    /// Labels the weakly connected components of a digraph, following every edge
+    /// on both directions.
+    pub(crate) fn weakly_connected<G: Graph<Node>>(graph: &G) -> Self {
+        let predecessors = predecessors(graph);
+
+        Self::label(graph.nodes(), |node| {
+            graph
+                .neighbors(node)
+                .chain(predecessors.get(&node).into_iter().flatten().copied())
+        })
+    }
+
+    /// Counts the nodes reached from `root`, following the edges yielded by `adjacent`.
+    fn reached_from<I>(root: Node, adjacent: impl Fn(Node) -> I) -> usize
+    where
+        I: Iterator<Item = Node>,
+    {
+        Self::label(iter::once(root), adjacent).sizes[0]
+    }
+
+    /// Returns the component id of `node`, or `None` if it's not a node of the graph.
+    pub fn component(&self, node: Node) -> Option<usize> {
+        self.component_of.get(&node).copied()
+    }
+
+    /// Returns the number of components.
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// Returns the number of nodes of each component, indexed by component id.
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    /// Returns `true` if `n` and `m` belong to the same component.
+    pub fn same_component(&self, n: Node, m: Node) -> bool {
+        match (self.component(n), self.component(m)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Components<usize> {
+    /// Labels the nodes `0..order` by the sets that the edges yielded by `edges`
+    /// join, merging both endpoints of every edge whatever its direction.
+    pub(crate) fn from_edges(order: usize, edges: impl Iterator<Item = (usize, usize)>) -> Self {
+        let mut sets = UnionFind::new(order);
+        for (n, m) in edges {
+            sets.union(n, m);
+        }
+
+        let mut id_of_root: HashMap<usize, usize> = HashMap::with_capacity(sets.set_count());
+        let mut component_of: HashMap<usize, usize> = HashMap::with_capacity(order);
+        let mut sizes: Vec<usize> = Vec::with_capacity(sets.set_count());
+        for node in 0..order {
+            let root = sets.find_mut(node);
+            let id = *id_of_root.entry(root).or_insert_with(|| {
+                sizes.push(0);
+                sizes.len() - 1
+            });
+            sizes[id] += 1;
+            component_of.insert(node, id);
+        }
+
+        Self {
+            component_of,
+            sizes,
+        }
+    }
+}
+
+/// Returns `true` if every node of `graph` can be reached from every other one
+/// following the direction of the edges.
+///
+/// That's the case exactly when the first node reaches every node, and every node
+/// reaches the first one, so it runs in linear time through two searches.
+pub(crate) fn reaches_every_node<Node, G>(graph: &G) -> bool
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    let Some(first) = graph.nodes().next() else {
+        return true;
+    };
+    if Components::reached_from(first, |node| graph.neighbors(node)) != graph.order() {
+        return false;
+    }
+
+    let predecessors = predecessors(graph);
+    Components::reached_from(first, |node| {
+        predecessors.get(&node).into_iter().flatten().copied()
+    }) == graph.order()
+}
+
+/// Maps every node of `graph` to the nodes with an edge to it, so that edges
+/// can be followed on both directions.
+pub(crate) fn predecessors<Node, G>(graph: &G) -> HashMap<Node, Vec<Node>>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    let mut predecessors: HashMap<Node, Vec<Node>> = HashMap::with_capacity(graph.order());
+    for n in graph.nodes() {
+        for m in graph.neighbors(n) {
+            predecessors.entry(m).or_default().push(n);
+        }
+    }
+    predecessors
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+
+    use crate::graphs::{AdjacencyList, AdjacencyMatrix, IncidenceMatrix};
+    use crate::{Graph, UndirectedGraph, UndirectedGraphIO};
+
+    static PATH: &str = "examples/data/";
+
+    #[test]
+    fn connected_components_of_undirected_graph() {
+        let result: Result<AdjacencyList, Error> =
+            UndirectedGraphIO::import_undirected_from_file(PATH.to_owned() + "GRAFO_2.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(list) = result {
+            let components = list.connected_components();
+            let matrix = AdjacencyMatrix::from_adjacency_list(&list);
+            let incidence = IncidenceMatrix::from_undirected_adjacency_matrix(&matrix);
+
+            assert!(components.count() > 1);
+            assert_eq!(components.sizes().iter().sum::<usize>(), list.order());
+            assert_eq!(components.component(0), Some(0));
+            assert_eq!(matrix.connected_components().sizes(), components.sizes());
+            assert_eq!(incidence.connected_components().sizes(), components.sizes());
+            assert!(!list.connected() && !matrix.connected() && !incidence.connected());
+        }
+    }
+
+    #[test]
+    fn components_ids_and_sizes() {
+        // Graph: 0 -- 1    2 -- 3 -- 4    5
+        let mut list = AdjacencyList(vec![vec![]; 6]);
+        list.add_undirected_edge(0, 1);
+        list.add_undirected_edge(2, 3);
+        list.add_undirected_edge(3, 4);
+
+        let components = list.connected_components();
+
+        assert_eq!(components.count(), 3);
+        assert_eq!(components.sizes(), &[2, 3, 1]);
+        assert_eq!(components.component(4), Some(1));
+        assert_eq!(components.component(6), None);
+        assert!(components.same_component(2, 4));
+        assert!(!components.same_component(1, 2));
+    }
+
+    #[test]
+    fn weakly_connected_components_of_digraph() {
+        // Graph: 0 -> 1 <- 2    3 -> 4    5
+        let list = AdjacencyList(vec![vec![1], vec![], vec![1], vec![4], vec![], vec![]]);
+
+        let weak = list.weakly_connected_components();
+
+        assert_eq!(weak.sizes(), &[3, 2, 1]);
+        assert!(weak.same_component(0, 2));
+        assert!(!weak.same_component(2, 3));
+    }
+
+    #[test]
+    fn connected_follows_the_direction_of_the_edges() {
+        // Graph: 0 <- 1 -> 2, where 0 reaches nothing.
+        let list = AdjacencyList(vec![vec![], vec![0, 2], vec![]]);
+        let matrix = AdjacencyMatrix::from_adjacency_list(&list);
+        let incidence = IncidenceMatrix::from_adjacency_matrix(&matrix);
+
+        assert!(!list.connected() && !matrix.connected() && !incidence.connected());
+        assert_eq!(list.weakly_connected_components().count(), 1);
+        assert_eq!(incidence.weakly_connected_components().count(), 1);
+
+        // Graph: 0 -> 1 -> 2 -> 0, whatever the first node is.
+        let cycle = AdjacencyList(vec![vec![1], vec![2], vec![0]]);
+        let incidence = IncidenceMatrix::from_directed_adjacency_list(&cycle);
+
+        assert!(cycle.connected() && incidence.connected());
+    }
+
+    #[test]
+    fn incidence_components_match_adjacency_list() {
+        // Graph: 0 -> 1 <- 2    3 -- 4    5, with a self-loop on 5.
+        let mut list = AdjacencyList(vec![vec![1], vec![], vec![1], vec![], vec![], vec![5]]);
+        list.add_undirected_edge(3, 4);
+        let incidence = IncidenceMatrix::from_directed_adjacency_list(&list);
+
+        let components = incidence.weakly_connected_components();
+
+        assert_eq!(
+            components.sizes(),
+            list.weakly_connected_components().sizes()
+        );
+        assert!(components.same_component(0, 2) && components.same_component(3, 4));
+        assert_eq!(
+            incidence.underlying_graph().connected_components().sizes(),
+            &[3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn empty_graph_is_connected() {
+        let list = AdjacencyList::default();
+
+        assert_eq!(list.connected_components().count(), 0);
+        assert!(list.connected());
+    }
+}
@@ -0,0 +1,649 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::Graph;
+use crate::graphs::AdjacencyList;
+use crate::weighted_graph::{Weight, WeightedGraph};
+
+/// Represents the **residual graph** of a flow network, whose nodes are `0..n`.
+///
+/// Every edge `(u -> v)` of the network is stored as an arc together with a reverse arc
+/// `(v -> u)`, and each arc holds its residual capacity: how much more flow it can take.
+/// The arcs are kept on an [`AdjacencyList`], so that the `k`-th arc leaving `u`
+/// reaches the `k`-th neighbor of `u`. Antiparallel edges get separate arcs.
+#[derive(Debug, Clone)]
+pub struct ResidualGraph<W> {
+    pub(crate) arcs: AdjacencyList,
+    pub(crate) capacity: Vec<Vec<W>>,
+    twin: Vec<Vec<usize>>,
+}
+
+impl<W: Weight> ResidualGraph<W> {
+    /// Creates a residual graph with `n` nodes and no arcs.
+    fn new(n: usize) -> Self {
+        Self {
+            arcs: AdjacencyList(vec![Vec::new(); n]),
+            capacity: vec![Vec::new(); n],
+            twin: vec![Vec::new(); n],
+        }
+    }
+
+    /// Returns the position of the reverse arc of the `k`-th arc leaving `u`,
+    /// on the arcs leaving its head.
+    pub(crate) fn twin(&self, u: usize, k: usize) -> usize {
+        self.twin[u][k]
+    }
+
+    /// Adds an arc `(u -> v)` with capacity `c` and its reverse arc with no capacity,
+    /// returning the position of the first one on the arcs leaving `u`.
+    fn add_arc(&mut self, u: usize, v: usize, c: W) -> usize {
+        let (k, j) = (self.arcs.0[u].len(), self.arcs.0[v].len());
+        self.arcs.0[u].push(v);
+        self.capacity[u].push(c);
+        self.twin[u].push(j);
+        self.arcs.0[v].push(u);
+        self.capacity[v].push(W::zero());
+        self.twin[v].push(k);
+        k
+    }
+
+    /// Sends `amount` of flow through the `k`-th arc leaving `u`.
+    pub(crate) fn push(&mut self, u: usize, k: usize, amount: W) {
+        let (v, j) = (self.arcs.0[u][k], self.twin[u][k]);
+        self.capacity[u][k] = self.capacity[u][k] - amount;
+        self.capacity[v][j] = self.capacity[v][j] + amount;
+    }
+
+    /// Returns the nodes reached from `u` through arcs with residual capacity left,
+    /// together with the position of that arc.
+    pub(crate) fn open_arcs(&self, u: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.arcs.0[u]
+            .iter()
+            .enumerate()
+            .filter(move |&(k, _)| self.capacity[u][k] > W::zero())
+            .map(|(k, &v)| (k, v))
+    }
+
+    /// Returns every arc, reverse arcs included, as an [`AdjacencyList`].
+    pub fn arcs(&self) -> &AdjacencyList {
+        &self.arcs
+    }
+
+    /// Returns the total residual capacity of the arcs from `u` to `v`.
+    pub fn residual_capacity(&self, u: usize, v: usize) -> W {
+        self.arcs
+            .neighbors(u)
+            .zip(self.capacity.get(u).into_iter().flatten())
+            .filter(|&(head, _)| head == v)
+            .fold(W::zero(), |total, (_, &c)| total + c)
+    }
+
+    /// Returns the arcs with residual capacity left, as an [`AdjacencyList`].
+    pub fn residual(&self) -> AdjacencyList {
+        AdjacencyList(
+            (0..self.arcs.order())
+                .map(|u| self.open_arcs(u).map(|(_, v)| v).collect())
+                .collect(),
+        )
+    }
+}
+
+/// Represents a **maximum flow** from a source to a sink, together with a **minimum cut**.
+///
+/// The cut splits the nodes into those still reachable from the source on the residual
+/// graph and the remaining ones. Its edges are saturated, and their capacities add up
+/// to the value of the flow.
+#[derive(Debug, Clone)]
+pub struct MaxFlow<Node, W> {
+    value: W,
+    flows: HashMap<(Node, Node), W>,
+    source_side: HashSet<Node>,
+    cut: Vec<(Node, Node)>,
+    residual: ResidualGraph<W>,
+}
+
+impl<Node, W> MaxFlow<Node, W>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+{
+    /// Returns the value of the flow: the net amount that leaves the source.
+    pub fn value(&self) -> W {
+        self.value
+    }
+
+    /// Returns the flow through the edge `(u -> v)`, or zero if there's no such edge.
+    pub fn flow(&self, u: Node, v: Node) -> W {
+        self.flows.get(&(u, v)).copied().unwrap_or(W::zero())
+    }
+
+    /// Returns the flow through every edge of the network.
+    pub fn flows(&self) -> &HashMap<(Node, Node), W> {
+        &self.flows
+    }
+
+    /// Returns the nodes on the source side of the minimum cut.
+    pub fn source_side(&self) -> &HashSet<Node> {
+        &self.source_side
+    }
+
+    /// Returns the edges that cross the minimum cut, from the source side to the sink side.
+    pub fn cut_edges(&self) -> &[(Node, Node)] {
+        &self.cut
+    }
+
+    /// Returns the residual graph left by the flow, whose node `i` is the `i`-th node
+    /// on [`Graph::nodes`] of the network.
+    pub fn residual(&self) -> &ResidualGraph<W> {
+        &self.residual
+    }
+}
+
+/// A flow network built from a capacity-weighted digraph, with nodes replaced by their
+/// position on [`Graph::nodes`].
+pub(crate) struct FlowNetwork<Node, W> {
+    pub(crate) nodes: Vec<Node>,
+    pub(crate) edges: Vec<(usize, usize, W)>,
+    pub(crate) residual: ResidualGraph<W>,
+    pub(crate) source: usize,
+    pub(crate) sink: usize,
+}
+
+impl<Node, W> FlowNetwork<Node, W>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+{
+    /// Builds the network, or returns `None` if `source` and `sink` aren't two distinct
+    /// nodes of `graph`. Self-loops are left out, as they never carry flow.
+    pub(crate) fn new<G: WeightedGraph<Node, W>>(
+        graph: &G,
+        source: Node,
+        sink: Node,
+    ) -> Option<Self> {
+        let nodes: Vec<Node> = graph.nodes().collect();
+        let index: HashMap<Node, usize> = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        let (&s, &t) = (index.get(&source)?, index.get(&sink)?);
+        if s == t {
+            return None;
+        }
+
+        let mut residual = ResidualGraph::new(nodes.len());
+        let mut edges = Vec::new();
+        for (u, &n) in nodes.iter().enumerate() {
+            for (m, c) in graph.weighted_neighbors(n) {
+                let v = index[&m];
+                if u != v {
+                    let k = residual.add_arc(u, v, c);
+                    edges.push((u, k, c));
+                }
+            }
+        }
+
+        Some(Self {
+            nodes,
+            edges,
+            residual,
+            source: s,
+            sink: t,
+        })
+    }
+
+    /// Returns the flow through each edge of the network, as `(u, k, flow)`
+    /// for the `k`-th arc leaving `u`.
+    pub(crate) fn edge_flows(&self) -> impl Iterator<Item = (usize, usize, W)> + '_ {
+        self.edges
+            .iter()
+            .map(|&(u, k, c)| (u, k, c - self.residual.capacity[u][k]))
+    }
+
+    /// Returns the net amount of flow that leaves the source.
+    pub(crate) fn value(&self) -> W {
+        let (mut out_flow, mut in_flow) = (W::zero(), W::zero());
+        for (u, k, flow) in self.edge_flows() {
+            if u == self.source {
+                out_flow = out_flow + flow;
+            } else if self.residual.arcs.0[u][k] == self.source {
+                in_flow = in_flow + flow;
+            }
+        }
+        out_flow - in_flow
+    }
+
+    /// Returns the flow through every edge, keyed by its ends.
+    pub(crate) fn flows(&self) -> HashMap<(Node, Node), W> {
+        self.edge_flows()
+            .map(|(u, k, flow)| {
+                (
+                    (self.nodes[u], self.nodes[self.residual.arcs.0[u][k]]),
+                    flow,
+                )
+            })
+            .collect()
+    }
+
+    /// Reads the flow and the minimum cut off the residual graph.
+    fn into_max_flow(self) -> MaxFlow<Node, W> {
+        let reachable = self.residual.residual().bfs_tree(self.source);
+        let source_side: HashSet<Node> = (0..self.nodes.len())
+            .filter(|&i| reachable.distance(i).is_some())
+            .map(|i| self.nodes[i])
+            .collect();
+
+        let cut = self
+            .edges
+            .iter()
+            .map(|&(u, k, _)| (self.nodes[u], self.nodes[self.residual.arcs.0[u][k]]))
+            .filter(|(n, m)| source_side.contains(n) && !source_side.contains(m))
+            .collect();
+
+        MaxFlow {
+            value: self.value(),
+            flows: self.flows(),
+            source_side,
+            cut,
+            residual: self.residual,
+        }
+    }
+}
+
+/// Returns the smaller of two weights.
+pub(crate) fn min<W: Weight>(a: W, b: W) -> W {
+    if b < a { b } else { a }
+}
+
+/// Computes a maximum flow with the **Edmonds-Karp algorithm**: the flow is repeatedly
+/// augmented through a shortest path on the residual graph, found by a BFS.
+pub(crate) fn edmonds_karp<Node, W, G>(
+    graph: &G,
+    source: Node,
+    sink: Node,
+) -> Option<MaxFlow<Node, W>>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    G: WeightedGraph<Node, W>,
+{
+    let mut network = FlowNetwork::new(graph, source, sink)?;
+    augment_along_shortest_paths(&mut network.residual, network.source, network.sink);
+    Some(network.into_max_flow())
+}
+
+/// Saturates `residual` from `s` to `t` through augmenting paths with the fewest arcs,
+/// as Edmonds-Karp does.
+pub(crate) fn augment_along_shortest_paths<W: Weight>(
+    residual: &mut ResidualGraph<W>,
+    s: usize,
+    t: usize,
+) {
+    let n = residual.arcs.order();
+
+    loop {
+        let mut parent: Vec<Option<(usize, usize)>> = vec![None; n];
+        let mut queue = VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            for (k, v) in residual.open_arcs(u) {
+                if v != s && parent[v].is_none() {
+                    parent[v] = Some((u, k));
+                    queue.push_back(v);
+                }
+            }
+            if parent[t].is_some() {
+                break;
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut current = t;
+        while let Some((u, k)) = parent[current] {
+            path.push((u, k));
+            current = u;
+        }
+        let Some(bottleneck) = path
+            .iter()
+            .map(|&(u, k)| residual.capacity[u][k])
+            .reduce(min)
+        else {
+            break;
+        };
+
+        for (u, k) in path {
+            residual.push(u, k, bottleneck);
+        }
+    }
+}
+
+/// Sends flow from `source` to `sink` along a path of the level graph, at most `limit`
+/// of it, and returns how much was sent.
+///
+/// `next` holds the first arc of each node that may still lead to the sink,
+/// so every dead end is skipped on later calls. The path is walked with an explicit
+/// stack, so long level graphs can't overflow the call stack.
+fn blocking_path<W: Weight>(
+    residual: &mut ResidualGraph<W>,
+    source: usize,
+    sink: usize,
+    limit: W,
+    level: &[usize],
+    next: &mut [usize],
+) -> W {
+    let mut path: Vec<(usize, usize)> = Vec::new();
+    let mut u = source;
+
+    while u != sink {
+        let arc = (next[u]..residual.arcs.0[u].len()).find(|&k| {
+            residual.capacity[u][k] > W::zero() && level[residual.arcs.0[u][k]] == level[u] + 1
+        });
+
+        match arc {
+            Some(k) => {
+                next[u] = k;
+                path.push((u, k));
+                u = residual.arcs.0[u][k];
+            }
+            None => {
+                // A dead end: step back and skip the arc that led here.
+                next[u] = residual.arcs.0[u].len();
+                let Some((prev, k)) = path.pop() else {
+                    return W::zero();
+                };
+                next[prev] = k + 1;
+                u = prev;
+            }
+        }
+    }
+
+    let sent = path
+        .iter()
+        .map(|&(u, k)| residual.capacity[u][k])
+        .fold(limit, min);
+    for (u, k) in path {
+        residual.push(u, k, sent);
+    }
+    sent
+}
+
+/// Computes a maximum flow with **Dinic's algorithm**: on every phase, a BFS splits the
+/// residual graph in levels, and a blocking flow is sent along the edges between levels.
+pub(crate) fn dinic<Node, W, G>(graph: &G, source: Node, sink: Node) -> Option<MaxFlow<Node, W>>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    G: WeightedGraph<Node, W>,
+{
+    let mut network = FlowNetwork::new(graph, source, sink)?;
+    let (s, t) = (network.source, network.sink);
+    let residual = &mut network.residual;
+    let n = residual.arcs.order();
+    let limit = residual.capacity[s]
+        .iter()
+        .fold(W::zero(), |total, &c| total + c);
+
+    loop {
+        let mut level = vec![usize::MAX; n];
+        level[s] = 0;
+        let mut queue = VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            for (_, v) in residual.open_arcs(u) {
+                if level[v] == usize::MAX {
+                    level[v] = level[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+        if level[t] == usize::MAX {
+            break;
+        }
+
+        let mut next = vec![0; n];
+        while blocking_path(residual, s, t, limit, &level, &mut next) > W::zero() {}
+    }
+
+    Some(network.into_max_flow())
+}
+
+/// Computes a maximum flow with the **push-relabel algorithm** (Goldberg-Tarjan),
+/// discharging the active nodes in FIFO order.
+///
+/// Instead of augmenting paths, it keeps a preflow, where nodes may hold excess flow,
+/// and pushes that excess towards the sink, or back to the source, following heights.
+pub(crate) fn push_relabel<Node, W, G>(
+    graph: &G,
+    source: Node,
+    sink: Node,
+) -> Option<MaxFlow<Node, W>>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    G: WeightedGraph<Node, W>,
+{
+    let mut network = FlowNetwork::new(graph, source, sink)?;
+    let (s, t) = (network.source, network.sink);
+    let residual = &mut network.residual;
+    let n = residual.arcs.order();
+
+    let mut height = vec![0; n];
+    let mut excess = vec![W::zero(); n];
+    let mut current = vec![0; n];
+    let mut active: VecDeque<usize> = VecDeque::new();
+
+    height[s] = n;
+    for k in 0..residual.arcs.0[s].len() {
+        let (v, c) = (residual.arcs.0[s][k], residual.capacity[s][k]);
+        if c > W::zero() {
+            residual.push(s, k, c);
+            if v != t && excess[v] == W::zero() {
+                active.push_back(v);
+            }
+            excess[v] = excess[v] + c;
+        }
+    }
+
+    while let Some(u) = active.pop_front() {
+        while excess[u] > W::zero() {
+            if current[u] == residual.arcs.0[u].len() {
+                height[u] = 1 + residual
+                    .open_arcs(u)
+                    .map(|(_, v)| height[v])
+                    .min()
+                    .unwrap_or(2 * n);
+                current[u] = 0;
+                continue;
+            }
+
+            let k = current[u];
+            let (v, c) = (residual.arcs.0[u][k], residual.capacity[u][k]);
+            if c > W::zero() && height[u] == height[v] + 1 {
+                let sent = min(excess[u], c);
+                residual.push(u, k, sent);
+                excess[u] = excess[u] - sent;
+                if v != s && v != t {
+                    if excess[v] == W::zero() {
+                        active.push_back(v);
+                    }
+                    excess[v] = excess[v] + sent;
+                }
+            } else {
+                current[u] += 1;
+            }
+        }
+    }
+
+    Some(network.into_max_flow())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::Error;
+
+    use super::MaxFlow;
+    use crate::graphs::WeightedAdjacencyList;
+    use crate::{Graph, WeightedGraph, WeightedGraphIO};
+
+    static PATH: &str = "examples/data/";
+
+    /// Checks the capacity and conservation constraints of `flow` on `graph`,
+    /// and that the minimum cut has the same capacity as the flow value.
+    fn is_max_flow(
+        graph: &WeightedAdjacencyList<i64>,
+        flow: &MaxFlow<usize, i64>,
+        source: usize,
+        sink: usize,
+    ) -> bool {
+        let mut balance: HashMap<usize, i64> = HashMap::new();
+        for (&(u, v), &f) in flow.flows() {
+            if f < 0 || Some(f) > graph.edge_weight(u, v) {
+                return false;
+            }
+            *balance.entry(u).or_default() -= f;
+            *balance.entry(v).or_default() += f;
+        }
+
+        let cut_capacity: i64 = flow
+            .cut_edges()
+            .iter()
+            .filter_map(|&(u, v)| graph.edge_weight(u, v))
+            .sum();
+
+        graph
+            .nodes()
+            .filter(|&n| n != source && n != sink)
+            .all(|n| balance.get(&n).copied().unwrap_or(0) == 0)
+            && balance.get(&sink).copied().unwrap_or(0) == flow.value()
+            && cut_capacity == flow.value()
+            && flow.source_side().contains(&source)
+            && !flow.source_side().contains(&sink)
+    }
+
+    /// Builds the classic network with 6 nodes and 10 edges whose maximum flow from 0 to 5 is 23.
+    fn example_network() -> WeightedAdjacencyList<i64> {
+        let mut graph = WeightedAdjacencyList(vec![vec![]; 6]);
+        for (u, v, c) in [
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (1, 2, 9),
+            (3, 2, 9),
+            (2, 4, 14),
+            (4, 3, 7),
+            (3, 5, 20),
+            (4, 5, 4),
+        ] {
+            graph.add_weighted_edge(u, v, c);
+        }
+        graph
+    }
+
+    #[test]
+    fn max_flow_of_network() {
+        let graph = example_network();
+
+        for flow in [
+            graph.edmonds_karp(0, 5),
+            graph.dinic(0, 5),
+            graph.push_relabel(0, 5),
+        ] {
+            assert!(flow.is_some());
+
+            if let Some(flow) = flow {
+                assert_eq!(flow.value(), 23);
+                assert!(is_max_flow(&graph, &flow, 0, 5));
+                assert_eq!(flow.residual().residual_capacity(3, 5), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn max_flow_of_weighted_digraph_file() {
+        let result: Result<WeightedAdjacencyList<i64>, Error> =
+            WeightedGraphIO::import_weighted_from_file(PATH.to_owned() + "DIGRAFO_PONDERADO.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(graph) = result {
+            // The cut {0, 1, 2, 3} holds the edges 0 -> 5 (14), 2 -> 5 (2) and 3 -> 4 (6).
+            for flow in [
+                graph.edmonds_karp(0, 5),
+                graph.dinic(0, 5),
+                graph.push_relabel(0, 5),
+            ] {
+                assert!(flow.is_some());
+
+                if let Some(flow) = flow {
+                    assert_eq!(flow.value(), 22);
+                    assert!(is_max_flow(&graph, &flow, 0, 5));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unreachable_sink_and_invalid_terminals() {
+        // 0 -(3)-> 1    2
+        let graph = WeightedAdjacencyList(vec![vec![(1, 3)], vec![], vec![]]);
+
+        for flow in [
+            graph.edmonds_karp(0, 2),
+            graph.dinic(0, 2),
+            graph.push_relabel(0, 2),
+        ] {
+            assert!(flow.is_some());
+
+            if let Some(flow) = flow {
+                assert_eq!(flow.value(), 0);
+                assert_eq!(flow.flow(0, 1), 0);
+                assert!(flow.cut_edges().is_empty());
+                assert_eq!(flow.source_side().len(), 2);
+            }
+        }
+
+        assert!(graph.dinic(0, 0).is_none());
+        assert!(graph.edmonds_karp(0, 7).is_none());
+    }
+
+    #[test]
+    fn dinic_on_a_long_path() {
+        // 0 -(2)-> 1 -(2)-> ... -(2)-> 99999, a single level graph as deep as the path.
+        let n = 100_000;
+        let graph = WeightedAdjacencyList(
+            (0..n)
+                .map(|u| if u + 1 < n { vec![(u + 1, 2)] } else { vec![] })
+                .collect(),
+        );
+
+        let flow = graph.dinic(0, n - 1);
+
+        assert!(flow.is_some());
+
+        if let Some(flow) = flow {
+            assert_eq!(flow.value(), 2);
+        }
+    }
+
+    #[test]
+    fn max_flow_with_float_capacities() {
+        // 0 -(1.5)-> 1 -(0.5)-> 3
+        // '-(2.0)-> 2 -(2.5)----^
+        let mut graph: WeightedAdjacencyList<f64> = WeightedAdjacencyList(vec![vec![]; 4]);
+        graph.add_weighted_edge(0, 1, 1.5);
+        graph.add_weighted_edge(1, 3, 0.5);
+        graph.add_weighted_edge(0, 2, 2.0);
+        graph.add_weighted_edge(2, 3, 2.5);
+
+        for flow in [
+            graph.edmonds_karp(0, 3),
+            graph.dinic(0, 3),
+            graph.push_relabel(0, 3),
+        ] {
+            assert!(flow.is_some());
+
+            if let Some(flow) = flow {
+                assert_eq!(flow.value(), 2.5);
+                assert_eq!(flow.flow(1, 3), 0.5);
+                assert_eq!(flow.cut_edges().len(), 2);
+            }
+        }
+    }
+}
@@ -1,7 +1,20 @@
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
+use crate::bipartite::{self, Bipartition, OddCycle};
+use crate::block_cut_tree::BlockCutTree;
+use crate::blossom;
+use crate::components::{self, Components};
+use crate::graphs::AdjacencyList;
+use crate::matching::{self, BipartiteMatching, Matching};
+use crate::scc::{self, KosarajuSccIter, TarjanSccIter};
+use crate::shortest_paths::{self, AStarSearch};
+use crate::topological::{self, AllTopologicalOrders, Cycle};
+use crate::visitor::{self, Visitor};
+use crate::weighted_graph::Weight;
+
 /// Defines a generic interface for a graph data structure.
 ///
 /// The [`Graph`] trait represents a **directed graph**, where each node can have
@@ -75,14 +88,46 @@ pub trait Graph<Node: Eq + Hash + Copy> {
     /// * `n` — The node whose outgoing neighbors are to be listed.
     fn neighbors<'a>(&'a self, n: Node) -> Self::Neighbors<'a>;
 
-    /// Checks whether the graph is **bipartite** and returns `true` or `false`
-    fn bipartite(&self) -> bool;
-
     /// Returns the **underlying graph** of the current structure.
     ///
     /// This removes edge directionality, making each edge bidirectional.
     fn underlying_graph(&self) -> Self;
 
+    /// Checks whether the graph is **bipartite** and returns `true` or `false`.
+    ///
+    /// See [`Graph::bipartition`] for the sides, or an odd cycle as the reason why not.
+    fn bipartite(&self) -> bool
+    where
+        Self: Sized,
+    {
+        self.bipartition().is_ok()
+    }
+
+    /// Splits the nodes into the two sides of a **bipartite** graph, ignoring the
+    /// direction of edges.
+    ///
+    /// # Returns
+    /// The two sides, or an [`OddCycle`] proving that the graph is not bipartite.
+    fn bipartition(&self) -> Result<Bipartition<Node>, OddCycle<Node>>
+    where
+        Self: Sized,
+    {
+        bipartite::bipartition(self)
+    }
+
+    /// Computes a **maximum matching** of a bipartite graph with the **Hopcroft-Karp algorithm**,
+    /// ignoring the direction of edges.
+    ///
+    /// # Returns
+    /// The matching, with the minimum vertex cover and the maximum independent set that follow
+    /// from Kőnig's theorem, or an [`OddCycle`] if the graph is not bipartite.
+    fn hopcroft_karp(&self) -> Result<BipartiteMatching<Node>, OddCycle<Node>>
+    where
+        Self: Sized,
+    {
+        matching::hopcroft_karp(self)
+    }
+
     /// Returns `true` if there is a directed edge from node `n` to node `m`.
     fn has_edge(&self, n: Node, m: Node) -> bool {
         self.neighbors(n).any(|neighbor| neighbor == m)
@@ -108,6 +153,145 @@ pub trait Graph<Node: Eq + Hash + Copy> {
         BfsIter::new(self, start)
     }
 
+    /// Returns the **BFS tree** from `start`, with the distance and parent of each reached node
+    /// and the nodes grouped by level.
+    fn bfs_tree(&self, start: Node) -> BfsTree<Node>
+    where
+        Self: Sized,
+    {
+        BfsTree::new(self, start)
+    }
+
+    /// Returns a path from `a` to `b` with the fewest edges, with both ends included,
+    /// or `None` if `b` can't be reached from `a`.
+    fn shortest_path(&self, a: Node, b: Node) -> Option<Vec<Node>>
+    where
+        Self: Sized,
+    {
+        self.bfs_tree(a).path_to(b)
+    }
+
+    /// Labels every node with the id of its **weakly connected component**, the connected
+    /// component it belongs to when the direction of the edges is ignored.
+    fn weakly_connected_components(&self) -> Components<Node>
+    where
+        Self: Sized,
+    {
+        Components::weakly_connected(self)
+    }
+
+    /// Returns the **reversed graph** (also called transpose), where every
+    /// edge `(n -> m)` becomes `(m -> n)`.
+    fn reversed(&self) -> Self
+    where
+        Self: Sized,
+    {
+        let mut reversed = Self::new_empty();
+        for n in self.nodes() {
+            reversed.add_node(n);
+        }
+        for n in self.nodes() {
+            for m in self.neighbors(n) {
+                reversed.add_edge(m, n);
+            }
+        }
+        reversed
+    }
+
+    /// Returns an iterator over the **strongly connected components** of the graph,
+    /// found by Tarjan's algorithm.
+    ///
+    /// The components are yielded in reverse topological order of the condensation.
+    fn tarjan_scc(&self) -> TarjanSccIter<'_, Node, Self>
+    where
+        Self: Sized,
+    {
+        TarjanSccIter::new(self)
+    }
+
+    /// Returns an iterator over the **strongly connected components** of the graph,
+    /// found by Kosaraju's algorithm.
+    ///
+    /// The components are yielded in reverse topological order of the condensation.
+    fn kosaraju_scc(&self) -> KosarajuSccIter<'_, Node, Self>
+    where
+        Self: Sized,
+    {
+        KosarajuSccIter::new(self)
+    }
+
+    /// Returns the **condensation** of the graph, a DAG with a node for each strongly
+    /// connected component, together with the component of each node.
+    ///
+    /// The components are numbered in topological order, so every edge of the
+    /// DAG goes from a smaller to a larger component.
+    fn condensation(&self) -> (AdjacencyList, HashMap<Node, usize>)
+    where
+        Self: Sized,
+    {
+        scc::condensation(self)
+    }
+
+    /// Returns a **topological order** of the nodes, found by Kahn's algorithm,
+    /// where every edge goes from a node to a later one.
+    ///
+    /// # Returns
+    /// The order, or a [`Cycle`] of the graph if it's not a DAG.
+    fn topological_sort(&self) -> Result<Vec<Node>, Cycle<Node>>
+    where
+        Self: Sized,
+    {
+        topological::kahn(self)
+    }
+
+    /// Returns a **topological order** of the nodes, found by sorting them
+    /// by decreasing finishing time ([`DfsEvent::Finish`]) of a DFS.
+    ///
+    /// # Returns
+    /// The order, or a [`Cycle`] of the graph if it's not a DAG.
+    fn topological_sort_dfs(&self) -> Result<Vec<Node>, Cycle<Node>>
+    where
+        Self: Sized,
+    {
+        topological::dfs(self)
+    }
+
+    /// Returns a lazy iterator over **every topological order** of the nodes.
+    ///
+    /// There can be up to `n!` orders, so this is meant for small graphs.
+    fn all_topological_orders(&self) -> AllTopologicalOrders<Node>
+    where
+        Self: Sized,
+    {
+        AllTopologicalOrders::new(self)
+    }
+
+    /// Searches a shortest path from `start` to `goal` using the **A\* algorithm**,
+    /// where every edge has the unit weight ([`Weight::one`]).
+    ///
+    /// The path found has the fewest edges; to follow edge weights instead,
+    /// see [`WeightedGraph::astar`](crate::WeightedGraph::astar).
+    ///
+    /// # Arguments
+    /// * `heuristic` - Estimates the distance from a node to `goal`. The path found is a
+    ///   shortest one as long as it never overestimates that distance.
+    fn unweighted_astar<W: Weight>(
+        &self,
+        start: Node,
+        goal: Node,
+        heuristic: impl Fn(Node) -> W,
+    ) -> AStarSearch<Node, W>
+    where
+        Self: Sized,
+    {
+        shortest_paths::astar(
+            start,
+            goal,
+            |n| self.neighbors(n).map(|m| (m, W::one())),
+            heuristic,
+        )
+    }
+
     /// Returns an iterator that classifies all edges encountered during a DFS traversal.
     ///
     /// The classification follows standard DFS rules, producing edges of type ['Edge']
@@ -117,6 +301,69 @@ pub trait Graph<Node: Eq + Hash + Copy> {
     {
         DfsEdgesIter::new(self, start)
     }
+
+    /// Performs a **depth-first search (DFS)** from `start`, calling the hooks of `visitor`.
+    ///
+    /// Each hook returns a [`Control`](crate::Control), which can prune part of the search
+    /// or stop it early.
+    ///
+    /// # Returns
+    /// The value given to [`Control::Break`](crate::Control::Break), or `None` if the
+    /// search wasn't stopped.
+    fn dfs_with<V: Visitor<Node>>(&self, start: Node, visitor: &mut V) -> Option<V::Break>
+    where
+        Self: Sized,
+    {
+        visitor::dfs_with(self, start, visitor)
+    }
+
+    /// Performs a **breadth-first search (BFS)** from `start`, calling the hooks of `visitor`.
+    ///
+    /// Each hook returns a [`Control`](crate::Control), which can prune part of the search
+    /// or stop it early. [`Visitor::back_edge`] is never called, since every edge to an
+    /// already discovered node goes to [`Visitor::non_tree_edge`].
+    ///
+    /// # Returns
+    /// The value given to [`Control::Break`](crate::Control::Break), or `None` if the
+    /// search wasn't stopped.
+    fn bfs_with<V: Visitor<Node>>(&self, start: Node, visitor: &mut V) -> Option<V::Break>
+    where
+        Self: Sized,
+    {
+        visitor::bfs_with(self, start, visitor)
+    }
+
+    /// Returns the **DFS tree** from `start`, with the discovery and finish timestamps,
+    /// the parent and the depth of each reached node.
+    fn dfs_tree(&self, start: Node) -> DfsTree<Node>
+    where
+        Self: Sized,
+    {
+        DfsEdgesIter::new(self, start).into_tree()
+    }
+
+    /// Returns an iterator that performs a **DFS over the whole graph**.
+    ///
+    /// A new search starts from each node not yet visited, in [`Graph::nodes`] order,
+    /// and each start is announced by a [`ForestEvent::Root`] before its [`DfsEvent`]s.
+    fn dfs_all(&self) -> DfsForestIter<'_, Node, Self>
+    where
+        Self: Sized,
+    {
+        DfsForestIter::new(self)
+    }
+
+    /// Returns an iterator that classifies **all edges of the graph** during a DFS
+    /// over the whole graph.
+    ///
+    /// A new search starts from each node not yet visited, in [`Graph::nodes`] order,
+    /// and each start is announced by a [`ForestEvent::Root`] before its [`Edge`]s.
+    fn classify_all_edges(&self) -> DfsEdgesForestIter<'_, Node, Self>
+    where
+        Self: Sized,
+    {
+        DfsEdgesForestIter::new(self)
+    }
 }
 
 /// Trait defining operations for **undirected graphs**.
@@ -131,7 +378,24 @@ pub trait UndirectedGraph<Node: Copy + Eq + Hash>: Graph<Node> {
     /// Checks whether the graph is **connected**.
     ///
     /// Returns `true` if there exists a path between every pair of nodes.
-    fn connected(&self) -> bool;
+    /// Edges stored on a single direction are only followed on that one, so a digraph
+    /// must be strongly connected; use [`Graph::weakly_connected_components`] to
+    /// ignore the directions instead. It runs in linear time through two searches.
+    fn connected(&self) -> bool
+    where
+        Self: Sized,
+    {
+        components::reaches_every_node(self)
+    }
+
+    /// Labels every node with the id of its **connected component**,
+    /// and counts the nodes of each component.
+    fn connected_components(&self) -> Components<Node>
+    where
+        Self: Sized,
+    {
+        Components::connected(self)
+    }
 
     /// Returns an iterator over the **biconnected components** of the graph.
     ///
@@ -143,6 +407,65 @@ pub trait UndirectedGraph<Node: Copy + Eq + Hash>: Graph<Node> {
         BiconnectedComponentsIter::new(self, start)
     }
 
+    /// Returns the **articulation points** (cut vertices) of the graph, in [`Graph::nodes`] order.
+    ///
+    /// An articulation point is a node whose removal increases the number of connected
+    /// components. Every connected component is searched, and a node is an articulation
+    /// point exactly when it belongs to more than one biconnected component.
+    fn articulation_points(&self) -> Vec<Node>
+    where
+        Self: Sized,
+    {
+        let mut blocks_of: HashMap<Node, usize> = HashMap::with_capacity(self.order());
+        for component in all_biconnected_components(self) {
+            let nodes: HashSet<Node> = component.into_iter().flat_map(|(u, v)| [u, v]).collect();
+            for node in nodes {
+                *blocks_of.entry(node).or_insert(0) += 1;
+            }
+        }
+
+        self.nodes()
+            .filter(|node| blocks_of.get(node).is_some_and(|&blocks| blocks > 1))
+            .collect()
+    }
+
+    /// Returns the **bridges** of the graph, the edges whose removal increases the number
+    /// of connected components.
+    ///
+    /// Every connected component is searched, and an edge is a bridge exactly when it's
+    /// a biconnected component by itself, so parallel edges are never bridges. Each bridge
+    /// `(u, v)` is oriented as the DFS tree edge from `u` to `v`.
+    fn bridges(&self) -> Vec<(Node, Node)>
+    where
+        Self: Sized,
+    {
+        all_biconnected_components(self)
+            .into_iter()
+            .filter(|component| component.len() == 1)
+            .map(|component| component[0])
+            .collect()
+    }
+
+    /// Returns the **block-cut tree** of the graph, with a node for each biconnected
+    /// component and for each articulation point.
+    fn block_cut_tree(&self) -> BlockCutTree<Node>
+    where
+        Self: Sized,
+    {
+        BlockCutTree::new(self)
+    }
+
+    /// Computes a **maximum cardinality matching** with **Edmonds' blossom algorithm**,
+    /// which, unlike [`Graph::hopcroft_karp`], also works on graphs with odd cycles.
+    ///
+    /// Each matched pair is given in [`Graph::nodes`] order.
+    fn maximum_matching(&self) -> Matching<Node>
+    where
+        Self: Sized,
+    {
+        blossom::maximum_matching(self)
+    }
+
     /// Adds an **undirected edge** `(n <-> m)` to the graph.
     ///
     /// Internally, this adds both directed edges `(n -> m)` and `(m -> n)`.
@@ -179,6 +502,27 @@ pub trait UndirectedGraph<Node: Copy + Eq + Hash>: Graph<Node> {
     }
 }
 
+/// Collects the biconnected components of every connected component of `graph`,
+/// restarting the search from each node not yet visited.
+pub(crate) fn all_biconnected_components<Node, G>(graph: &G) -> Vec<Vec<(Node, Node)>>
+where
+    Node: Eq + Hash + Copy,
+    G: UndirectedGraph<Node>,
+{
+    let mut roots = graph.nodes();
+    let Some(first) = roots.next() else {
+        return Vec::new();
+    };
+
+    let mut iter = graph.biconnected_components(first);
+    let mut components: Vec<Vec<(Node, Node)>> = iter.by_ref().collect();
+    for root in roots {
+        iter.new_start(root);
+        components.extend(iter.by_ref());
+    }
+    components
+}
+
 /// Represents an event that occurs during a depth-first search (DFS) traversal.
 ///
 /// This enum is used to describe the different types of events that can be
@@ -228,6 +572,16 @@ where
         }
     }
 
+    /// Creates a DFS iterator without a start node, to be set later with [`DfsIter::new_start`].
+    pub(crate) fn unstarted(graph: &'a G) -> Self {
+        Self {
+            graph,
+            stack: vec![],
+            visited: HashSet::with_capacity(graph.order()),
+            start_node: None,
+        }
+    }
+
     /// Sets the `start_node` field of a `DfsIter` manually.
     ///
     /// This enables starting another DFS while maintains the inner parts of the iterator
@@ -296,6 +650,7 @@ pub struct BfsIter<'a, Node, G> {
     queue: VecDeque<Node>,
     visited: HashSet<Node>,
     parent: HashMap<Node, Option<Node>>,
+    depth: HashMap<Node, usize>,
 }
 
 impl<'a, Node, G> BfsIter<'a, Node, G>
@@ -311,13 +666,25 @@ where
         let mut parent: HashMap<Node, Option<Node>> = HashMap::with_capacity(graph.order());
         parent.insert(start, None);
 
+        let mut depth: HashMap<Node, usize> = HashMap::with_capacity(graph.order());
+        depth.insert(start, 0);
+
         Self {
             graph,
             queue: VecDeque::from(vec![start]),
             visited,
             parent,
+            depth,
         }
     }
+
+    /// Returns the depth of a node on the BFS tree, which is its distance from the start node.
+    ///
+    /// A node has a depth as soon as it's listed as a child on a [`BfsEvent::Discover`],
+    /// so the depth of every node seen so far is available while iterating.
+    pub fn depth(&self, node: Node) -> Option<usize> {
+        self.depth.get(&node).copied()
+    }
 }
 
 impl<'a, Node, G> Iterator for BfsIter<'a, Node, G>
@@ -336,6 +703,7 @@ where
             if self.visited.insert(neighbor) {
                 self.queue.push_back(neighbor);
                 self.parent.insert(neighbor, Some(node));
+                self.depth.insert(neighbor, self.depth[&node] + 1);
                 children.push(neighbor);
             } else if Some(node) != self.parent.get(&neighbor).copied().flatten() {
                 events.push(BfsEvent::CrossEdge(node, neighbor));
@@ -347,6 +715,84 @@ where
     }
 }
 
+/// Represents the tree built by a breadth-first search (BFS) from a start node.
+///
+/// It holds the distance (number of edges) from the start to every reached node,
+/// the parent of each node on the tree and the nodes grouped by level.
+#[derive(Debug, Clone)]
+pub struct BfsTree<Node> {
+    distances: HashMap<Node, usize>,
+    parents: HashMap<Node, Node>,
+    levels: Vec<Vec<Node>>,
+}
+
+impl<Node: Eq + Hash + Copy> BfsTree<Node> {
+    /// Builds the BFS tree of `graph` from `start`.
+    fn new<G: Graph<Node>>(graph: &G, start: Node) -> Self {
+        let mut distances = HashMap::with_capacity(graph.order());
+        let mut parents = HashMap::with_capacity(graph.order());
+        let mut levels = vec![vec![start]];
+        distances.insert(start, 0);
+
+        while let Some(level) = levels.last() {
+            let mut next_level = Vec::new();
+            for &node in level {
+                for neighbor in graph.neighbors(node) {
+                    if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                        entry.insert(levels.len());
+                        parents.insert(neighbor, node);
+                        next_level.push(neighbor);
+                    }
+                }
+            }
+            if next_level.is_empty() {
+                break;
+            }
+            levels.push(next_level);
+        }
+
+        Self {
+            distances,
+            parents,
+            levels,
+        }
+    }
+
+    /// Returns the number of edges from the start to `node`, or `None` if it wasn't reached.
+    pub fn distance(&self, node: Node) -> Option<usize> {
+        self.distances.get(&node).copied()
+    }
+
+    /// Returns the parent of `node` on the tree, or `None` if it's the start or wasn't reached.
+    pub fn parent(&self, node: Node) -> Option<Node> {
+        self.parents.get(&node).copied()
+    }
+
+    /// Returns the reached nodes grouped by their distance from the start,
+    /// so `levels()[d]` holds every node at distance `d`.
+    pub fn levels(&self) -> &[Vec<Node>] {
+        &self.levels
+    }
+
+    /// Rebuilds the path from the start to `target` on the tree, with both ends included.
+    ///
+    /// Returns `None` if `target` wasn't reached.
+    pub fn path_to(&self, target: Node) -> Option<Vec<Node>> {
+        if !self.distances.contains_key(&target) {
+            return None;
+        }
+
+        let mut path = vec![target];
+        let mut current = target;
+        while let Some(&parent) = self.parents.get(&current) {
+            path.push(parent);
+            current = parent;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
 /// Represents the classification of an edge in a graph during a depth-first search (DFS).
 ///
 /// This enum is used to categorize edges based on the DFS traversal. It is generic
@@ -369,6 +815,63 @@ pub enum Edge<Node> {
     Cross(Node, Node),
 }
 
+/// Represents the forest built by a depth-first search (DFS).
+///
+/// Each reached node has a discovery and a finish timestamp, taken from a single clock
+/// that ticks on every discovery and every finish. By the parenthesis theorem, the
+/// intervals `[discovery, finish]` of two nodes are either disjoint or nested, and
+/// they're nested exactly when one node is an ancestor of the other.
+#[derive(Debug, Clone)]
+pub struct DfsTree<Node> {
+    discovery: HashMap<Node, usize>,
+    finish: HashMap<Node, usize>,
+    parent: HashMap<Node, Node>,
+    depth: HashMap<Node, usize>,
+}
+
+impl<Node: Eq + Hash + Copy> DfsTree<Node> {
+    /// Returns the time when `node` was discovered, or `None` if it wasn't reached.
+    pub fn discovery(&self, node: Node) -> Option<usize> {
+        self.discovery.get(&node).copied()
+    }
+
+    /// Returns the time when `node` was finished, or `None` if it wasn't reached.
+    pub fn finish(&self, node: Node) -> Option<usize> {
+        self.finish.get(&node).copied()
+    }
+
+    /// Returns the `[discovery, finish]` interval of `node`, or `None` if it wasn't reached.
+    pub fn interval(&self, node: Node) -> Option<(usize, usize)> {
+        Some((self.discovery(node)?, self.finish(node)?))
+    }
+
+    /// Returns the parent of `node` on the tree, or `None` if it's a root or wasn't reached.
+    pub fn parent(&self, node: Node) -> Option<Node> {
+        self.parent.get(&node).copied()
+    }
+
+    /// Returns the number of tree edges from the root to `node`,
+    /// or `None` if it wasn't reached.
+    pub fn depth(&self, node: Node) -> Option<usize> {
+        self.depth.get(&node).copied()
+    }
+
+    /// Returns `true` if `u` is an ancestor of `v` on the tree, checked in `O(1)`
+    /// by the nesting of their intervals. Every node is an ancestor of itself.
+    pub fn is_ancestor(&self, u: Node, v: Node) -> bool {
+        match (self.interval(u), self.interval(v)) {
+            (Some((du, fu)), Some((dv, fv))) => du <= dv && fv <= fu,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if `u` is a descendant of `v` on the tree.
+    /// Every node is a descendant of itself.
+    pub fn is_descendant(&self, u: Node, v: Node) -> bool {
+        self.is_ancestor(v, u)
+    }
+}
+
 /// An iterator that performs a depth-first search (DFS) and classifies the edges of the graph.
 ///
 /// This iterator wraps a `DfsIter` and uses its events to classify each edge of the
@@ -385,6 +888,7 @@ where
     discovery: HashMap<Node, usize>,
     finish: HashMap<Node, usize>,
     parent: HashMap<Node, Node>,
+    depth: HashMap<Node, usize>,
     stack_hash: HashSet<Node>,
 }
 
@@ -395,12 +899,21 @@ where
 {
     /// Creates a new DFS-with-edges iterator starting from the given node.
     fn new(graph: &'a G, start: Node) -> Self {
+        let mut iter = Self::unstarted(graph);
+        iter.new_start(start);
+        iter
+    }
+
+    /// Creates a DFS-with-edges iterator without a start node, to be set later
+    /// with [`DfsEdgesIter::new_start`].
+    fn unstarted(graph: &'a G) -> Self {
         Self {
-            iter: DfsIter::new(graph, start),
+            iter: DfsIter::unstarted(graph),
             time: 0,
             discovery: HashMap::with_capacity(graph.order()),
             finish: HashMap::with_capacity(graph.order()),
             parent: HashMap::with_capacity(graph.order()),
+            depth: HashMap::with_capacity(graph.order()),
             stack_hash: HashSet::with_capacity(graph.order()),
         }
     }
@@ -411,6 +924,18 @@ where
     pub fn new_start(&mut self, start: Node) {
         self.iter.new_start(start);
     }
+
+    /// Finishes the traversal and returns the resulting [`DfsTree`], with the
+    /// timestamps, parent and depth of every node reached.
+    pub fn into_tree(mut self) -> DfsTree<Node> {
+        self.by_ref().for_each(drop);
+        DfsTree {
+            discovery: self.discovery,
+            finish: self.finish,
+            parent: self.parent,
+            depth: self.depth,
+        }
+    }
 }
 
 impl<'a, Node, G> Iterator for DfsEdgesIter<'a, Node, G>
@@ -427,6 +952,8 @@ where
                     self.stack_hash.insert(node);
                     self.discovery.insert(node, self.time);
                     self.time += 1;
+                    let depth = maybe_parent.map_or(0, |parent| self.depth[&parent] + 1);
+                    self.depth.insert(node, depth);
                     if let Some(parent) = maybe_parent {
                         self.parent.insert(node, parent);
                         return Some(Edge::Tree(parent, node));
@@ -464,6 +991,132 @@ where
     }
 }
 
+/// Represents an event of a traversal over the whole graph, which is a forest of searches.
+///
+/// # Variants
+/// - `Root(Node)`: Indicates that a new search starts from `Node`, which wasn't reached
+///   by the previous ones.
+/// - `Event(E)`: An event of the current search, like a [`DfsEvent`] or an [`Edge`].
+#[derive(Debug)]
+pub enum ForestEvent<Node, E> {
+    Root(Node),
+    Event(E),
+}
+
+/// Represents an iterator over a DFS traversal of the whole graph.
+///
+/// The iteration yields a [`ForestEvent`] wrapping each [`DfsEvent`] of the inner `DfsIter`.
+pub struct DfsForestIter<'a, Node, G>
+where
+    G: Graph<Node>,
+    Node: Eq + Hash + Copy,
+    Self: 'a,
+{
+    iter: DfsIter<'a, Node, G>,
+    roots: std::vec::IntoIter<Node>,
+}
+
+impl<'a, Node, G> DfsForestIter<'a, Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    /// Creates a new DFS iterator over the whole graph.
+    fn new(graph: &'a G) -> Self {
+        let roots: Vec<Node> = graph.nodes().collect();
+        Self {
+            iter: DfsIter::unstarted(graph),
+            roots: roots.into_iter(),
+        }
+    }
+}
+
+impl<'a, Node, G> Iterator for DfsForestIter<'a, Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    type Item = ForestEvent<Node, DfsEvent<Node>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.iter.next() {
+            return Some(ForestEvent::Event(event));
+        }
+
+        let visited = &self.iter.visited;
+        let root = self.roots.find(|root| !visited.contains(root))?;
+        self.iter.new_start(root);
+        Some(ForestEvent::Root(root))
+    }
+}
+
+/// Represents an iterator that classifies every edge of the graph during a DFS
+/// traversal of the whole graph.
+///
+/// The iteration yields a [`ForestEvent`] wrapping each [`Edge`] of the inner `DfsEdgesIter`.
+pub struct DfsEdgesForestIter<'a, Node, G>
+where
+    G: Graph<Node>,
+    Node: Eq + Hash + Copy,
+    Self: 'a,
+{
+    iter: DfsEdgesIter<'a, Node, G>,
+    roots: std::vec::IntoIter<Node>,
+}
+
+impl<'a, Node, G> DfsEdgesForestIter<'a, Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    /// Creates a new DFS-with-edges iterator over the whole graph.
+    fn new(graph: &'a G) -> Self {
+        let roots: Vec<Node> = graph.nodes().collect();
+        Self {
+            iter: DfsEdgesIter::unstarted(graph),
+            roots: roots.into_iter(),
+        }
+    }
+}
+
+impl<'a, Node, G> DfsEdgesForestIter<'a, Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    /// Finishes the traversal and returns the resulting [`DfsTree`], which is a forest
+    /// covering every node of the graph.
+    pub fn into_tree(self) -> DfsTree<Node> {
+        let Self { mut iter, roots } = self;
+        // The current tree has to be finished before starting from another root.
+        iter.by_ref().for_each(drop);
+        for root in roots {
+            iter.new_start(root);
+            iter.by_ref().for_each(drop);
+        }
+        iter.into_tree()
+    }
+}
+
+impl<'a, Node, G> Iterator for DfsEdgesForestIter<'a, Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    type Item = ForestEvent<Node, Edge<Node>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(edge) = self.iter.next() {
+            return Some(ForestEvent::Event(edge));
+        }
+
+        let visited = &self.iter.iter.visited;
+        let root = self.roots.find(|root| !visited.contains(root))?;
+        self.iter.new_start(root);
+        Some(ForestEvent::Root(root))
+    }
+}
+
 /// An iterator that yields the biconnected components of a undirected graph (`UndirectedGraph`).
 ///
 /// The iterator identifies the biconnected components during a depth-first-search (DFS) that's
@@ -479,6 +1132,7 @@ where
     discovery: HashMap<Node, usize>,
     lowpt: HashMap<Node, usize>,
     parents: HashMap<Node, Node>,
+    tree_edges_back: HashSet<Node>,
     edge_stack: Vec<(Node, Node)>,
 }
 
@@ -495,16 +1149,26 @@ where
             discovery: HashMap::with_capacity(graph.order()),
             lowpt: HashMap::with_capacity(graph.order()),
             parents: HashMap::with_capacity(graph.order()),
+            tree_edges_back: HashSet::with_capacity(graph.order()),
             edge_stack: Vec::with_capacity(graph.order()),
         }
     }
 
-    /// Extracts a biconnected component from the edge stack.
+    /// Sets the start node of the inner `DfsIter` manually.
+    ///
+    /// This enables finding the biconnected components of another component of the graph.
+    pub fn new_start(&mut self, start: Node) {
+        self.iter.new_start(start);
+    }
+
+    /// Extracts a biconnected component from the edge stack, down to the tree edge `(u, v)`.
+    ///
+    /// Only that orientation is matched, since `(v, u)` may be a parallel back edge.
     fn extract_component(&mut self, u: Node, v: Node) -> Option<Vec<(Node, Node)>> {
         let mut component = Vec::new();
         while let Some(edge) = self.edge_stack.pop() {
             component.push(edge);
-            if edge == (u, v) || edge == (v, u) {
+            if edge == (u, v) {
                 break;
             }
         }
@@ -515,7 +1179,7 @@ where
 impl<'a, Node, G> Iterator for BiconnectedComponentsIter<'a, Node, G>
 where
     G: Graph<Node>,
-    Node: Eq + Hash + Copy + Display,
+    Node: Eq + Hash + Copy,
 {
     type Item = Vec<(Node, Node)>;
 
@@ -546,7 +1210,12 @@ where
                     }
                 }
                 DfsEvent::NonTreeEdge(u, v) => {
-                    if Some(&v) != self.parents.get(&u) && self.discovery[&v] < self.discovery[&u] {
+                    // The first edge back to the parent is the tree edge itself, but
+                    // a parallel copy of it is a back edge.
+                    if Some(&v) == self.parents.get(&u) && self.tree_edges_back.insert(u) {
+                        continue;
+                    }
+                    if self.discovery[&v] < self.discovery[&u] {
                         self.edge_stack.push((u, v));
                         self.lowpt
                             .entry(u)
@@ -561,7 +1230,10 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::{DfsEvent, Graph, UndirectedGraph, graphs::AdjacencyList};
+    use crate::{
+        BfsEvent, DfsEvent, Edge, ForestEvent, Graph, UndirectedGraph,
+        graphs::{AdjacencyList, IncidenceMatrix},
+    };
 
     #[test]
     fn dfs_with_cycle() {
@@ -641,4 +1313,206 @@ mod test {
         assert!(components.contains(&vec![(3, 1), (2, 3), (1, 2)]));
         assert!(components.contains(&vec![(0, 1)]));
     }
+
+    #[test]
+    fn bfs_tree_levels() {
+        // 0 -> 1 -> 3 -> 4
+        //  \        ^
+        //   -> 2 --/     5
+        let graph = AdjacencyList(vec![vec![1, 2], vec![3], vec![3], vec![4], vec![], vec![]]);
+
+        let tree = graph.bfs_tree(0);
+
+        assert_eq!(tree.levels(), &[vec![0], vec![1, 2], vec![3], vec![4]]);
+        assert_eq!(tree.distance(4), Some(3));
+        assert_eq!(tree.distance(5), None);
+        assert_eq!(tree.parent(3), Some(1));
+        assert_eq!(tree.parent(0), None);
+        assert_eq!(tree.path_to(4), Some(vec![0, 1, 3, 4]));
+    }
+
+    #[test]
+    fn unweighted_shortest_path() {
+        // 0 -> 1 -> 2 -> 3
+        //  \____________^
+        let graph = AdjacencyList(vec![vec![1, 3], vec![2], vec![3], vec![]]);
+
+        assert_eq!(graph.shortest_path(0, 3), Some(vec![0, 3]));
+        assert_eq!(graph.shortest_path(1, 3), Some(vec![1, 2, 3]));
+        assert_eq!(graph.shortest_path(2, 2), Some(vec![2]));
+        assert_eq!(graph.shortest_path(3, 0), None);
+    }
+
+    #[test]
+    fn bfs_depth_of_discovered_nodes() {
+        let graph = AdjacencyList(vec![vec![1, 2], vec![3], vec![3], vec![]]);
+
+        let mut bfs = graph.bfs(0);
+        let mut depths = Vec::new();
+        while let Some(events) = bfs.next() {
+            for event in events {
+                if let BfsEvent::Discover(node, _) = event {
+                    depths.push((node, bfs.depth(node)));
+                }
+            }
+        }
+
+        assert_eq!(
+            depths,
+            vec![(0, Some(0)), (1, Some(1)), (2, Some(1)), (3, Some(2))]
+        );
+    }
+
+    #[test]
+    fn dfs_all_restarts_on_every_tree() {
+        // 0 -> 1    2 -> 0    3
+        let g = AdjacencyList(vec![vec![1], vec![], vec![0], vec![]]);
+
+        let roots: Vec<usize> = g
+            .dfs_all()
+            .filter_map(|event| match event {
+                ForestEvent::Root(root) => Some(root),
+                ForestEvent::Event(_) => None,
+            })
+            .collect();
+        let discovered = g
+            .dfs_all()
+            .filter(|event| matches!(event, ForestEvent::Event(DfsEvent::Discover(_, _))))
+            .count();
+
+        assert_eq!(roots, vec![0, 2, 3]);
+        assert_eq!(discovered, 4);
+    }
+
+    #[test]
+    fn classify_all_edges_in_one_call() {
+        // 0 -> 1 -> 2 -> 0    3 -> 1
+        let g = AdjacencyList(vec![vec![1], vec![2], vec![0], vec![1]]);
+
+        let mut all = g.classify_all_edges();
+
+        assert!(matches!(all.next(), Some(ForestEvent::Root(0))));
+        assert!(matches!(
+            all.next(),
+            Some(ForestEvent::Event(Edge::Tree(0, 1)))
+        ));
+        assert!(matches!(
+            all.next(),
+            Some(ForestEvent::Event(Edge::Tree(1, 2)))
+        ));
+        assert!(matches!(
+            all.next(),
+            Some(ForestEvent::Event(Edge::Back(2, 0)))
+        ));
+        assert!(matches!(all.next(), Some(ForestEvent::Root(3))));
+        assert!(matches!(
+            all.next(),
+            Some(ForestEvent::Event(Edge::Cross(3, 1)))
+        ));
+        assert!(all.next().is_none());
+    }
+
+    #[test]
+    fn dfs_tree_parenthesis_structure() {
+        // 0 -> 1 -> 2
+        // |    '--> 3
+        // '-> 4    5 -> 1
+        let g = AdjacencyList(vec![
+            vec![1, 4],
+            vec![2, 3],
+            vec![],
+            vec![],
+            vec![],
+            vec![1],
+        ]);
+
+        let tree = g.dfs_tree(0);
+
+        assert_eq!(tree.interval(0), Some((0, 9)));
+        assert_eq!(tree.interval(2), Some((2, 3)));
+        assert_eq!(tree.interval(5), None);
+        assert_eq!(tree.parent(3), Some(1));
+        assert_eq!(tree.depth(3), Some(2));
+        assert_eq!(tree.depth(4), Some(1));
+        assert!(tree.is_ancestor(0, 3));
+        assert!(tree.is_descendant(2, 1));
+        assert!(!tree.is_ancestor(4, 2));
+        assert!(!tree.is_ancestor(2, 3));
+
+        let forest = g.classify_all_edges().into_tree();
+
+        assert_eq!(forest.depth(5), Some(0));
+        assert_eq!(forest.parent(5), None);
+        for u in g.nodes() {
+            for v in g.nodes() {
+                let (Some((du, fu)), Some((dv, fv))) = (forest.interval(u), forest.interval(v))
+                else {
+                    panic!("every node should be reached");
+                };
+                let disjoint = fu < dv || fv < du;
+                assert!(disjoint || forest.is_ancestor(u, v) || forest.is_ancestor(v, u));
+            }
+        }
+    }
+
+    #[test]
+    fn forest_into_tree_after_some_events() {
+        // 0 -> 2 -> 3    1
+        //      '--> 0
+        let g = AdjacencyList(vec![vec![2], vec![], vec![3, 0], vec![]]);
+
+        // Node 1 must not be started before the tree of 0 is finished.
+        let mut events = g.classify_all_edges();
+        assert!(matches!(events.next(), Some(ForestEvent::Root(0))));
+        let partial = events.into_tree();
+        let full = g.classify_all_edges().into_tree();
+
+        for n in g.nodes() {
+            assert_eq!(partial.interval(n), full.interval(n));
+            assert_eq!(partial.parent(n), full.parent(n));
+        }
+        assert_eq!(partial.interval(0), Some((0, 5)));
+        assert_eq!(partial.interval(1), Some((6, 7)));
+    }
+
+    #[test]
+    fn articulation_points_and_bridges() {
+        // 0 -- 1 -- 4      5 -- 6 -- 7
+        //    /  \               \  /
+        //   3 -- 2                8
+        let mut graph = AdjacencyList(vec![vec![]; 9]);
+        for (u, v) in [
+            (0, 1),
+            (1, 4),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (5, 6),
+            (6, 7),
+            (6, 8),
+            (7, 8),
+        ] {
+            graph.add_undirected_edge(u, v);
+        }
+
+        assert_eq!(graph.articulation_points(), vec![1, 6]);
+
+        let mut bridges: Vec<(usize, usize)> = graph
+            .bridges()
+            .into_iter()
+            .map(|(u, v)| (u.min(v), u.max(v)))
+            .collect();
+        bridges.sort();
+
+        assert_eq!(bridges, vec![(0, 1), (1, 4), (5, 6)]);
+    }
+
+    #[test]
+    fn parallel_edges_are_not_bridges() {
+        // 0 == 1 -- 2, with two edges between 0 and 1.
+        let matrix = IncidenceMatrix::from_rows(vec![vec![1, 1, 0], vec![1, 1, 0], vec![0, 1, 1]]);
+
+        assert_eq!(matrix.bridges(), vec![(1, 2)]);
+        assert_eq!(matrix.articulation_points(), vec![1]);
+    }
 }
@@ -3,8 +3,10 @@ use std::fmt::Display;
 use std::fs::File;
 use std::hash::Hash;
 use std::io::{BufRead, BufReader, Error, ErrorKind, Write};
+use std::str::FromStr;
 
 use crate::graph::BfsEvent;
+use crate::weighted_graph::{Weight, WeightedGraph};
 use crate::{DfsEvent, Edge, Graph, UndirectedGraph};
 
 /// Provides input/output capabilities for a directed graph.
@@ -14,18 +16,20 @@ use crate::{DfsEvent, Edge, Graph, UndirectedGraph};
 /// (BFS and DFS) to DOT files for visualization and analysis.
 /// # Type Parameters
 /// - `Node`: The trait is generic over the node type `Node`,
-///   which must implement basic traits like `Copy`,
-///   `Eq`, `Hash`, `Display`, and `From<usize>` (the `From<usize>` requirement will be removed
-///   in the future when non-integer node types are supported).
-pub trait GraphIO<Node: Copy + Eq + Hash + Display + From<usize>>: Graph<Node> {
+///   which must implement basic traits like `Copy`, `Eq`, `Hash` and `Display`.
+///   Importing from a file also requires `From<usize>`, since nodes are read as numbers.
+pub trait GraphIO<Node: Copy + Eq + Hash + Display>: Graph<Node> {
     /// Imports a graph from a file. The file should define the number of nodes
     /// on the first line and edges on subsequent lines.
+    /// An optional third column with the edge weight must be a number and is ignored,
+    /// and any other number of columns is invalid.
     ///
     /// # Arguments
     /// * `path` - The path to the input file containing the graph.
     fn import_from_file(path: String) -> Result<Self, Error>
     where
         Self: Sized,
+        Node: From<usize>,
     {
         let mut graph = Self::new_empty();
         let file = File::open(path).unwrap();
@@ -43,18 +47,8 @@ pub trait GraphIO<Node: Copy + Eq + Hash + Display + From<usize>>: Graph<Node> {
                     graph.add_node(Node::from(i));
                 }
             } else {
-                let values: Vec<usize> = content
-                    .split(',')
-                    .map(|x| {
-                        x.trim().parse::<usize>().map(|v| v - 1).map_err(|_| {
-                            Error::new(
-                                ErrorKind::InvalidData,
-                                format!("Invalid data was found during file creation: {} ", x),
-                            )
-                        })
-                    })
-                    .collect::<Result<Vec<_>, Error>>()?;
-                graph.add_edge(Node::from(values[0]), Node::from(values[1]));
+                let (n, m, _) = parse_edge_line::<f64>(&content)?;
+                graph.add_edge(Node::from(n), Node::from(m));
             }
         }
 
@@ -134,7 +128,6 @@ pub trait GraphIO<Node: Copy + Eq + Hash + Display + From<usize>>: Graph<Node> {
     fn export_directed_dfs_to_dot(&self, start: Node, mut path: String) -> Result<(), Error>
     where
         Self: Sized,
-        Node: Copy + Eq + Hash + Display + From<usize>,
     {
         if !path.contains(".dot") {
             path += ".dot";
@@ -182,18 +175,20 @@ pub trait GraphIO<Node: Copy + Eq + Hash + Display + From<usize>>: Graph<Node> {
 /// to undirected-specific operations.
 /// # Type Parameters
 /// - `Node`: The trait is generic over the node type `Node`,
-///   which must implement basic traits like `Copy`,
-///   `Eq`, `Hash`, `Display`, and `From<usize>` (the `From<usize>` requirement will be removed
-///   in the future when non-integer node types are supported).
-pub trait UndirectedGraphIO<Node: Copy + Eq + Hash + Display + From<usize>>: GraphIO<Node> {
+///   which must implement basic traits like `Copy`, `Eq`, `Hash` and `Display`.
+///   Importing from a file also requires `From<usize>`, since nodes are read as numbers.
+pub trait UndirectedGraphIO<Node: Copy + Eq + Hash + Display>: GraphIO<Node> {
     /// Imports an undirected graph from a file. The file should define the number
     /// of nodes on the first line and edges on subsequent lines.
+    /// An optional third column with the edge weight must be a number and is ignored,
+    /// and any other number of columns is invalid.
     ///
     /// # Arguments
     /// * `path` - The path to the input file containing the undirected graph.
     fn import_undirected_from_file(path: String) -> Result<Self, Error>
     where
         Self: Sized + UndirectedGraph<Node>,
+        Node: From<usize>,
     {
         let mut graph = Self::new_empty();
         let file = File::open(path).unwrap();
@@ -214,19 +209,8 @@ pub trait UndirectedGraphIO<Node: Copy + Eq + Hash + Display + From<usize>>: Gra
                     graph.add_node(Node::from(i));
                 }
             } else {
-                let values: Vec<usize> = content
-                    .split(',')
-                    .map(|x| {
-                        x.trim().parse::<usize>().map(|v| v - 1).map_err(|_| {
-                            Error::new(
-                                ErrorKind::InvalidData,
-                                format!("Invalid node was found during file creation: {} ", x),
-                            )
-                        })
-                    })
-                    .collect::<Result<Vec<_>, Error>>()?;
-
-                graph.add_undirected_edge(Node::from(values[0]), Node::from(values[1]));
+                let (n, m, _) = parse_edge_line::<f64>(&content)?;
+                graph.add_undirected_edge(Node::from(n), Node::from(m));
             }
         }
 
@@ -276,9 +260,8 @@ pub trait UndirectedGraphIO<Node: Copy + Eq + Hash + Display + From<usize>>: Gra
     fn export_undirected_dfs_to_dot(&self, start: Node, mut path: String) -> Result<(), Error>
     where
         Self: Sized + UndirectedGraph<Node>,
-        Node: Copy + Eq + Hash + Display + From<usize> + PartialOrd,
     {
-        if Node::from(self.nodes().count()) < start {
+        if !self.nodes().any(|node| node == start) {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
                 format!("Node {} isn't present in graph!", start),
@@ -333,9 +316,8 @@ pub trait UndirectedGraphIO<Node: Copy + Eq + Hash + Display + From<usize>>: Gra
     fn export_undirected_bfs_to_dot(&self, start: Node, mut path: String) -> Result<(), Error>
     where
         Self: Sized + UndirectedGraph<Node>,
-        Node: Copy + Eq + Hash + Display + From<usize> + PartialOrd,
     {
-        if Node::from(self.nodes().count()) < start {
+        if !self.nodes().any(|node| node == start) {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
                 format!("Node {} isn't present in graph!", start),
@@ -380,3 +362,171 @@ pub trait UndirectedGraphIO<Node: Copy + Eq + Hash + Display + From<usize>>: Gra
         Ok(())
     }
 }
+
+/// Provides input capabilities for a weighted graph.
+///
+/// This trait extends `GraphIO` with methods that read the optional third column
+/// of each edge line as the weight of that edge. Edges without that column get
+/// the unit weight ([`Weight::one`]). A weight the graph can't hold is invalid, like `0`
+/// on an [`AdjacencyMatrix`](crate::graphs::AdjacencyMatrix), where it marks a missing edge.
+/// # Type Parameters
+/// - `Node`: The node type, like on [`GraphIO`].
+/// - `W`: The weight type, which must implement [`FromStr`] to be read from the file.
+pub trait WeightedGraphIO<Node: Copy + Eq + Hash + Display, W: Weight + FromStr>:
+    GraphIO<Node> + WeightedGraph<Node, W>
+{
+    /// Imports a weighted graph from a file. The file should define the number of nodes
+    /// on the first line and edges on subsequent lines, as `source,target[,weight]`.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the input file containing the graph.
+    fn import_weighted_from_file(path: String) -> Result<Self, Error>
+    where
+        Self: Sized,
+        Node: From<usize>,
+    {
+        let mut graph = Self::new_empty();
+        let reader = BufReader::new(File::open(path)?);
+        read_weighted_edges(reader, &mut graph, |graph, n, m, w| {
+            graph.add_weighted_edge(Node::from(n), Node::from(m), w)
+        })?;
+        Ok(graph)
+    }
+
+    /// Imports an undirected weighted graph from a file. The file should define the number
+    /// of nodes on the first line and edges on subsequent lines, as `node,node[,weight]`.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the input file containing the undirected graph.
+    fn import_undirected_weighted_from_file(path: String) -> Result<Self, Error>
+    where
+        Self: Sized + UndirectedGraph<Node>,
+        Node: From<usize>,
+    {
+        let mut graph = Self::new_empty();
+        let reader = BufReader::new(File::open(path)?);
+        read_weighted_edges(reader, &mut graph, |graph, n, m, w| {
+            graph.add_undirected_weighted_edge(Node::from(n), Node::from(m), w)
+        })?;
+        Ok(graph)
+    }
+}
+
+/// Reads the lines of a weighted graph file, adding its nodes to `graph` and calling `add_edge`
+/// with the zero-based endpoints and the weight of every edge line.
+/// An edge the graph doesn't hold afterwards is an error, since its weight was lost.
+fn read_weighted_edges<Node, W, G>(
+    reader: impl BufRead,
+    graph: &mut G,
+    mut add_edge: impl FnMut(&mut G, usize, usize, W),
+) -> Result<(), Error>
+where
+    Node: Copy + Eq + Hash + From<usize>,
+    W: Weight + FromStr,
+    G: Graph<Node>,
+{
+    for (idx, line) in reader.lines().enumerate() {
+        let content = line?;
+        if content.is_empty() {
+            continue;
+        }
+
+        if idx == 0 {
+            let n: usize = content
+                .parse()
+                .map_err(|_| Error::new(ErrorKind::InvalidData, "Line 1 from file is invalid"))?;
+
+            for i in 0..n {
+                graph.add_node(Node::from(i));
+            }
+        } else {
+            let (n, m, w) = parse_edge_line::<W>(&content)?;
+            let w = w.unwrap_or_else(W::one);
+            add_edge(graph, n, m, w);
+            if !graph.has_edge(Node::from(n), Node::from(m)) {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid weight was found during file creation: {:?} ", w),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses an edge line as `source,target[,weight]`, returning the zero-based
+/// endpoints and the weight, if the line has one.
+fn parse_edge_line<W: FromStr>(content: &str) -> Result<(usize, usize, Option<W>), Error> {
+    let invalid = |what: &str, x: &str| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("Invalid {} was found during file creation: {} ", what, x),
+        )
+    };
+    let node = |x: &str| {
+        x.trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|v| v.checked_sub(1))
+            .ok_or_else(|| invalid("data", x))
+    };
+    let weight = |x: &str| x.trim().parse::<W>().map_err(|_| invalid("weight", x));
+
+    match content.split(',').collect::<Vec<_>>().as_slice() {
+        [n, m] => Ok((node(n)?, node(m)?, None)),
+        [n, m, w] => Ok((node(n)?, node(m)?, Some(weight(w)?))),
+        _ => Err(invalid("edge", content)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use super::*;
+    use crate::graphs::AdjacencyMatrix;
+
+    #[test]
+    fn edge_line_with_and_without_weight() {
+        assert_eq!(parse_edge_line::<i32>("1,2").unwrap(), (0, 1, None));
+        assert_eq!(
+            parse_edge_line::<i32>(" 3 , 1 , -4").unwrap(),
+            (2, 0, Some(-4))
+        );
+        assert_eq!(
+            parse_edge_line::<f64>("2,2,0.5").unwrap(),
+            (1, 1, Some(0.5))
+        );
+    }
+
+    #[test]
+    fn invalid_edge_lines() {
+        for (line, message) in [
+            ("1,x", "Invalid data was found"),
+            ("0,1", "Invalid data was found"),
+            ("1,2,heavy", "Invalid weight was found"),
+            ("1", "Invalid edge was found"),
+            ("1,2,3,4", "Invalid edge was found"),
+        ] {
+            let err = parse_edge_line::<f64>(line).unwrap_err();
+
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+            assert!(err.to_string().contains(message), "{line}: {err}");
+        }
+    }
+
+    #[test]
+    fn zero_weight_on_adjacency_matrix_is_rejected() {
+        let mut matrix = AdjacencyMatrix::new_empty();
+        let add = |graph: &mut AdjacencyMatrix, n, m, w| graph.add_weighted_edge(n, m, w);
+
+        assert!(read_weighted_edges("3\n1,2,4\n2,3\n".as_bytes(), &mut matrix, add).is_ok());
+        assert_eq!(matrix.edge_weight(0, 1), Some(4));
+
+        let err = read_weighted_edges("3\n1,2,0\n".as_bytes(), &mut matrix, add).unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().contains("Invalid weight was found"));
+    }
+}
@@ -1,17 +1,54 @@
+use crate::graph_io::UndirectedGraphIO;
 use crate::graphs::{AdjacencyList, AdjacencyMatrix};
-use crate::{Graph, UndirectedGraph};
+use crate::{Components, Graph, GraphIO, UndirectedGraph};
 
 /// Represents a graph using an incidence matrix.
 /// Each row corresponds to an edge, and each column corresponds to a node.
 /// Cell values indicate the relationship between the edge and the node:
 /// `-1` for the source node, `1` for the target node, and `0` otherwise.
-/// # Note
-/// Many functions in this struct are currently `to-do`.
-/// The focus is on adjacency list and adjacency matrix for now.
+/// Undirected edges are stored with `1` on both endpoints and a self-loop is
+/// stored as a single `1` on its node.
+///
+/// The order of the graph is kept apart from the rows, so nodes without any
+/// incident edge are still part of it.
 #[derive(Debug, Clone)]
-pub struct IncidenceMatrix(pub Vec<Vec<i32>>);
+pub struct IncidenceMatrix(Vec<Vec<i32>>, usize);
 
 impl IncidenceMatrix {
+    /// Constructs an incidence matrix of `order` nodes from its edge rows.
+    ///
+    /// # Arguments
+    /// * `rows` - One row per edge, with a cell per node.
+    /// * `order` - The number of nodes of the graph.
+    ///
+    /// # Panics
+    /// If a row doesn't have exactly `order` cells.
+    pub fn new(rows: Vec<Vec<i32>>, order: usize) -> Self {
+        assert!(
+            rows.iter().all(|row| row.len() == order),
+            "every row of the incidence matrix must have a cell per node"
+        );
+        IncidenceMatrix(rows, order)
+    }
+
+    /// Constructs an incidence matrix from its edge rows, taking the order of the graph
+    /// from the length of the rows. Without any row, the graph is empty.
+    ///
+    /// # Arguments
+    /// * `rows` - One row per edge, with a cell per node.
+    ///
+    /// # Panics
+    /// If the rows don't all have the same length.
+    pub fn from_rows(rows: Vec<Vec<i32>>) -> Self {
+        let order = rows.first().map_or(0, |row| row.len());
+        Self::new(rows, order)
+    }
+
+    /// Returns the edge rows of the matrix, each with a cell per node.
+    pub fn rows(&self) -> &[Vec<i32>] {
+        &self.0
+    }
+
     /// Constructs an incidence matrix from an adjacency matrix.
     ///
     /// # Arguments
@@ -22,7 +59,7 @@ impl IncidenceMatrix {
         let mut edges: Vec<(usize, usize)> = Vec::new();
         for i in 0..n {
             for j in 0..n {
-                if matrix.0[i][j] == 1 {
+                if matrix.0[i][j] != 0 {
                     edges.push((i, j));
                 }
             }
@@ -36,7 +73,7 @@ impl IncidenceMatrix {
             inc[i][c] = 1;
         }
 
-        IncidenceMatrix(inc)
+        IncidenceMatrix(inc, n)
     }
 
     /// Constructs an incidence matrix from an adjacency list representing a directed graph.
@@ -57,7 +94,7 @@ impl IncidenceMatrix {
             }
         }
 
-        IncidenceMatrix(incidence_matrix)
+        IncidenceMatrix(incidence_matrix, adj_list.order())
     }
 
     /// Constructs an incidence matrix from an adjacency list representing an undirected graph.
@@ -78,117 +115,260 @@ impl IncidenceMatrix {
             }
         }
 
-        IncidenceMatrix(incidence_matrix)
+        IncidenceMatrix(incidence_matrix, adj_list.order())
     }
-}
 
-impl Graph<usize> for IncidenceMatrix {
-    fn new_empty() -> Self {
-        IncidenceMatrix(Vec::new())
-    }
+    /// Constructs an incidence matrix from an adjacency matrix representing an undirected graph.
+    ///
+    /// Each undirected edge becomes a single row with `1` on both endpoints,
+    /// so only the upper triangle of the matrix (with its diagonal) is read.
+    ///
+    /// # Arguments
+    /// * `matrix` - The adjacency matrix of an undirected graph.
+    pub fn from_undirected_adjacency_matrix(matrix: &AdjacencyMatrix) -> Self {
+        let n = matrix.0.len();
+        let mut inc: Vec<Vec<i32>> = Vec::new();
 
-    fn order(&self) -> usize {
-        if self.0.is_empty() {
-            0
-        } else {
-            self.0[0].len()
+        for i in 0..n {
+            for j in i..n {
+                if matrix.0[i][j] != 0 {
+                    let mut edge: Vec<i32> = vec![0; n];
+                    edge[i] = 1;
+                    edge[j] = 1;
+                    inc.push(edge);
+                }
+            }
         }
-    }
 
-    fn size(&self) -> usize {
-        self.0.len()
+        IncidenceMatrix(inc, n)
     }
 
-    fn node_degrees(&self, _n: usize) -> (usize, usize) {
-        todo!()
+    /// Returns an iterator over every directed edge `(source, target)` stored in the matrix.
+    ///
+    /// Undirected rows yield both directions and self-loops are yielded once.
+    /// Malformed rows are skipped.
+    pub(crate) fn directed_edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.0
+            .iter()
+            .filter_map(|row| RowEdge::from_row(row))
+            .flat_map(|edge| match edge {
+                RowEdge::Directed(u, v) => [Some((u, v)), None],
+                RowEdge::Undirected(u, v) => [Some((u, v)), Some((v, u))],
+                RowEdge::Loop(u) => [Some((u, u)), None],
+            })
+            .flatten()
     }
 
-    fn nodes(&self) -> impl Iterator<Item = usize> {
-        0..self.order()
+    /// Returns every edge row joining `n` and `m`, in any direction.
+    fn rows_between(&self, n: usize, m: usize) -> impl Iterator<Item = usize> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(move |(idx, row)| match RowEdge::from_row(row)? {
+                RowEdge::Directed(u, v) | RowEdge::Undirected(u, v)
+                    if (u, v) == (n, m) || (u, v) == (m, n) =>
+                {
+                    Some(idx)
+                }
+                RowEdge::Loop(u) if u == n && u == m => Some(idx),
+                _ => None,
+            })
     }
 
-    fn add_node(&mut self, _n: usize) {
-        todo!()
+    /// Builds a new edge row with the given values on each endpoint.
+    fn edge_row(&self, cells: &[(usize, i32)]) -> Vec<i32> {
+        let mut row = vec![0; self.order()];
+        for &(node, value) in cells {
+            row[node] = value;
+        }
+        row
     }
+}
 
-    fn remove_node(&mut self, _n: usize) {
-        todo!()
-    }
+/// The edge stored on a single row of an [`IncidenceMatrix`].
+enum RowEdge {
+    /// An edge from the `-1` node to the `1` node.
+    Directed(usize, usize),
+    /// An edge with `1` on both endpoints.
+    Undirected(usize, usize),
+    /// A row with a single non-zero cell.
+    Loop(usize),
+}
 
-    fn add_edge(&mut self, _n: usize, _m: usize) {
-        todo!()
+impl RowEdge {
+    /// Reads the edge stored on `row`, returning `None` if the row is malformed.
+    fn from_row(row: &[i32]) -> Option<Self> {
+        let mut cells = row
+            .iter()
+            .enumerate()
+            .filter(|&(_, &value)| value != 0)
+            .map(|(node, &value)| (node, value));
+
+        match (cells.next(), cells.next(), cells.next()) {
+            (Some((u, -1)), Some((v, 1)), None) => Some(RowEdge::Directed(u, v)),
+            (Some((u, 1)), Some((v, -1)), None) => Some(RowEdge::Directed(v, u)),
+            (Some((u, 1)), Some((v, 1)), None) => Some(RowEdge::Undirected(u, v)),
+            (Some((u, _)), None, None) => Some(RowEdge::Loop(u)),
+            _ => None,
+        }
     }
 
-    fn remove_edge(&mut self, _n: usize, _m: usize) {
-        todo!()
+    /// Returns the node reached from `n` through this edge, if it can be followed from `n`.
+    fn follow(&self, n: usize) -> Option<usize> {
+        match *self {
+            RowEdge::Directed(u, v) if u == n => Some(v),
+            RowEdge::Undirected(u, v) if u == n => Some(v),
+            RowEdge::Undirected(u, v) if v == n => Some(u),
+            RowEdge::Loop(u) if u == n => Some(u),
+            _ => None,
+        }
     }
+}
 
-    type Neighbors<'a> = std::iter::FilterMap<
-        std::iter::Enumerate<std::slice::Iter<'a, usize>>,
-        fn((usize, &'a usize)) -> Option<usize>,
-    >;
+/// Iterator over the neighbors of a node in an [`IncidenceMatrix`].
+///
+/// It walks the rows of the matrix and yields the other endpoint of every
+/// edge that leaves the node: the `1` column of rows where the node is `-1`,
+/// the other endpoint of undirected rows and the node itself for self-loops.
+pub struct IncidenceNeighbors<'a> {
+    rows: std::slice::Iter<'a, Vec<i32>>,
+    node: usize,
+}
 
-    fn neighbors<'a>(&'a self, _n: usize) -> Self::Neighbors<'a> {
-        todo!()
-    }
+impl<'a> Iterator for IncidenceNeighbors<'a> {
+    type Item = usize;
 
-    fn bipartite(&self) -> bool {
-        let n = self.order();
-        if n == 0 {
-            return true;
+    fn next(&mut self) -> Option<Self::Item> {
+        for row in self.rows.by_ref() {
+            if row.get(self.node).is_none_or(|&value| value == 0) {
+                continue;
+            }
+            if let Some(neighbor) = RowEdge::from_row(row).and_then(|edge| edge.follow(self.node)) {
+                return Some(neighbor);
+            }
         }
+        None
+    }
+}
 
-        let mut adj = vec![Vec::new(); n];
+impl Graph<usize> for IncidenceMatrix {
+    fn new_empty() -> Self {
+        IncidenceMatrix(Vec::new(), 0)
+    }
 
-        for edge in &self.0 {
-            let endpoints: Vec<usize> = edge
-                .iter()
-                .enumerate()
-                .filter_map(|(v, &x)| if x != 0 { Some(v) } else { None })
-                .collect();
+    fn order(&self) -> usize {
+        self.1
+    }
 
-            match endpoints.as_slice() {
-                [u, v] => {
-                    adj[*u].push(*v);
-                    adj[*v].push(*u);
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+
+    fn node_degrees(&self, n: usize) -> (usize, usize) {
+        let mut in_deg = 0;
+        let mut out_deg = 0;
+
+        for edge in self.0.iter().filter_map(|row| RowEdge::from_row(row)) {
+            match edge {
+                RowEdge::Directed(u, v) => {
+                    out_deg += (u == n) as usize;
+                    in_deg += (v == n) as usize;
                 }
-                [u] => {
-                    adj[*u].push(*u);
+                RowEdge::Undirected(u, v) if u == n || v == n => {
+                    in_deg += 1;
+                    out_deg += 1;
+                }
+                RowEdge::Loop(u) if u == n => {
+                    in_deg += 1;
+                    out_deg += 1;
                 }
                 _ => {}
             }
         }
+        (in_deg, out_deg)
+    }
 
-        let mut partition = vec![None; n];
-        let mut queue = std::collections::VecDeque::new();
+    fn nodes(&self) -> impl Iterator<Item = usize> {
+        0..self.order()
+    }
 
-        for start in 0..n {
-            if partition[start].is_some() {
-                continue;
-            }
+    fn add_node(&mut self, n: usize) {
+        if n < self.order() {
+            return;
+        }
+        for row in self.0.iter_mut() {
+            row.resize(n + 1, 0);
+        }
+        self.1 = n + 1;
+    }
 
-            partition[start] = Some(0);
-            queue.push_back(start);
+    fn remove_node(&mut self, n: usize) {
+        if n < self.order() {
+            self.0.retain(|row| row[n] == 0);
+            for row in self.0.iter_mut() {
+                row.remove(n);
+            }
+            self.1 -= 1;
+        }
+    }
 
-            while let Some(u) = queue.pop_front() {
-                let u_side = partition[u].unwrap();
+    fn add_edge(&mut self, n: usize, m: usize) {
+        if n < self.order() && m < self.order() && !self.has_edge(n, m) {
+            let row = if n == m {
+                self.edge_row(&[(n, 1)])
+            } else {
+                self.edge_row(&[(n, -1), (m, 1)])
+            };
+            self.0.push(row);
+        }
+    }
 
-                for &v in &adj[u] {
-                    if partition[v].is_none() {
-                        partition[v] = Some(1 - u_side);
-                        queue.push_back(v);
-                    } else if partition[v] == Some(u_side) {
-                        return false;
-                    }
-                }
+    fn remove_edge(&mut self, n: usize, m: usize) {
+        let Some(idx) = self
+            .rows_between(n, m)
+            .find(|&idx| RowEdge::from_row(&self.0[idx]).is_some_and(|e| e.follow(n) == Some(m)))
+        else {
+            return;
+        };
+
+        match RowEdge::from_row(&self.0[idx]) {
+            // Only the `n -> m` direction goes away, `m -> n` is kept as a directed edge.
+            Some(RowEdge::Undirected(_, _)) if n != m => {
+                self.0[idx] = self.edge_row(&[(m, -1), (n, 1)]);
+            }
+            _ => {
+                self.0.remove(idx);
             }
         }
+    }
 
-        true
+    type Neighbors<'a> = IncidenceNeighbors<'a>;
+
+    fn neighbors<'a>(&'a self, n: usize) -> Self::Neighbors<'a> {
+        IncidenceNeighbors {
+            rows: self.0.iter(),
+            node: n,
+        }
+    }
+
+    /// Merges the endpoints of every row on a single pass over the matrix,
+    /// instead of scanning all the rows for the neighbors of each node.
+    fn weakly_connected_components(&self) -> Components<usize> {
+        Components::from_edges(self.order(), self.directed_edges())
     }
 
     fn underlying_graph(&self) -> Self {
-        todo!()
+        let mut matrix = IncidenceMatrix(Vec::new(), self.order());
+
+        for edge in self.0.iter().filter_map(|row| RowEdge::from_row(row)) {
+            match edge {
+                RowEdge::Directed(u, v) | RowEdge::Undirected(u, v) => {
+                    matrix.add_undirected_edge(u, v)
+                }
+                RowEdge::Loop(u) => matrix.add_undirected_edge(u, u),
+            }
+        }
+        matrix
     }
 }
 
@@ -197,23 +377,59 @@ impl UndirectedGraph<usize> for IncidenceMatrix {
         self.0.len()
     }
 
+    /// Reads the rows once into adjacency lists, so both searches run in linear time.
     fn connected(&self) -> bool {
-        todo!()
+        let mut successors = vec![Vec::new(); self.order()];
+        for (n, m) in self.directed_edges() {
+            successors[n].push(m);
+        }
+        AdjacencyList(successors).connected()
+    }
+
+    /// Merges the endpoints of every row on a single pass over the matrix.
+    fn connected_components(&self) -> Components<usize> {
+        Components::from_edges(self.order(), self.directed_edges())
     }
 
     fn undirected_node_degree(&self, vertex: usize) -> usize {
-        if self.0.is_empty() || vertex >= self.0[0].len() {
+        if vertex >= self.order() {
             return 0;
         }
 
         self.0.iter().filter(|row| row[vertex] != 0).count()
     }
+
+    fn add_undirected_edge(&mut self, n: usize, m: usize) {
+        if n >= self.order() || m >= self.order() || (self.has_edge(n, m) && self.has_edge(m, n)) {
+            return;
+        }
+
+        let directed: Vec<usize> = self.rows_between(n, m).collect();
+        for idx in directed.into_iter().rev() {
+            self.0.remove(idx);
+        }
+
+        let row = if n == m {
+            self.edge_row(&[(n, 1)])
+        } else {
+            self.edge_row(&[(n, 1), (m, 1)])
+        };
+        self.0.push(row);
+    }
 }
 
+impl GraphIO<usize> for IncidenceMatrix {}
+
+impl UndirectedGraphIO<usize> for IncidenceMatrix {}
+
 #[cfg(test)]
 mod tests {
+    use std::io::Error;
+
     use super::*;
 
+    static PATH: &str = "examples/data/";
+
     #[test]
     fn test_undirected_node_degree() {
         // Graph: 0 ── 1 ── 2
@@ -227,7 +443,7 @@ mod tests {
         //   [1, 1, 0],
         //   [0, 1, 1]
         // ]
-        let incidence = IncidenceMatrix(vec![vec![1, 1, 0], vec![0, 1, 1]]);
+        let incidence = IncidenceMatrix::from_rows(vec![vec![1, 1, 0], vec![0, 1, 1]]);
 
         assert_eq!(incidence.undirected_node_degree(0), 1); // connected (0–1)
         assert_eq!(incidence.undirected_node_degree(1), 2); // connected (0–1) e (1–2)
@@ -237,7 +453,7 @@ mod tests {
     #[test]
     fn test_size_incidence_matrix_direct() {
         // Graph: 0 ── 1 ── 2
-        let incidence = IncidenceMatrix(vec![
+        let incidence = IncidenceMatrix::from_rows(vec![
             vec![1, 1, 0], // 1 to 0 and 1
             vec![0, 1, 1], // 2 to 1 and 2
         ]);
@@ -287,4 +503,241 @@ mod tests {
 
         assert_eq!(inc.0, answer);
     }
+
+    #[test]
+    fn new_digraph_1() {
+        let result: Result<IncidenceMatrix, Error> =
+            GraphIO::import_from_file(PATH.to_owned() + "DIGRAFO1.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(matrix) = result {
+            assert_eq!(matrix.order(), 13);
+            assert_eq!(matrix.size(), 16);
+        }
+    }
+
+    #[test]
+    fn new_undirected_graph_1() {
+        let result: Result<IncidenceMatrix, Error> =
+            UndirectedGraphIO::import_undirected_from_file(PATH.to_owned() + "GRAFO_2.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(matrix) = result {
+            assert_eq!(matrix.order(), 11);
+            assert_eq!(matrix.undirected_size(), 13);
+            assert!(!matrix.connected());
+        }
+    }
+
+    #[test]
+    fn graph_add_nodes_and_edges() {
+        // Graph: 0 -> 1 -> 2
+        let mut matrix = IncidenceMatrix::new_empty();
+        matrix.add_node(0);
+        matrix.add_node(1);
+        matrix.add_node(2);
+        matrix.add_edge(0, 1);
+        matrix.add_edge(1, 2);
+        matrix.add_edge(1, 2);
+
+        assert_eq!(matrix.order(), 3);
+        assert_eq!(matrix.size(), 2);
+        assert_eq!(matrix.0, vec![vec![-1, 1, 0], vec![0, -1, 1]]);
+        assert!(matrix.has_edge(0, 1));
+        assert!(!matrix.has_edge(1, 0));
+        assert_eq!(matrix.neighbors(1).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn from_rows_takes_the_order_from_the_rows() {
+        // Graph: 0 -> 1    2
+        let matrix = IncidenceMatrix::from_rows(vec![vec![-1, 1, 0]]);
+
+        assert_eq!(matrix.order(), 3);
+        assert_eq!(matrix.nodes().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert!(matrix.has_edge(0, 1));
+        assert_eq!(IncidenceMatrix::from_rows(Vec::new()).order(), 0);
+    }
+
+    #[test]
+    fn add_node_keeps_a_cell_per_node() {
+        // Graph: 0 -- 1
+        let mut matrix = IncidenceMatrix::from_rows(vec![vec![1, 1]]);
+        matrix.add_node(1);
+
+        assert_eq!(matrix.order(), 2);
+
+        matrix.add_node(3);
+
+        // Current graph: 0 -- 1    2    3
+        assert_eq!(matrix.order(), 4);
+        assert_eq!(matrix.rows(), &[vec![1, 1, 0, 0]]);
+        assert_eq!(matrix.undirected_node_degree(3), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "a cell per node")]
+    fn new_rejects_rows_of_another_order() {
+        IncidenceMatrix::new(vec![vec![1, 1, 0]], 4);
+    }
+
+    #[test]
+    fn neighbors_of_undirected_and_loop_edges() {
+        // Graph: 0 -- 1 -> 2, with a loop on 2
+        let matrix = IncidenceMatrix::from_rows(vec![vec![1, 1, 0], vec![0, -1, 1], vec![0, 0, 1]]);
+
+        assert_eq!(matrix.neighbors(0).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(matrix.neighbors(1).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(matrix.neighbors(2).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(matrix.node_degrees(1), (1, 2));
+        assert_eq!(matrix.node_degrees(2), (2, 1));
+    }
+
+    #[test]
+    fn graph_remove_edge() {
+        // Graph: 0 -- 1 -> 2
+        let mut matrix = IncidenceMatrix::from_rows(vec![vec![1, 1, 0], vec![0, -1, 1]]);
+        matrix.remove_edge(0, 1);
+        matrix.remove_edge(1, 2);
+
+        // Current graph: 0 <- 1    2
+        assert_eq!(matrix.0, vec![vec![1, -1, 0]]);
+        assert!(matrix.has_edge(1, 0));
+        assert!(!matrix.has_edge(0, 1));
+    }
+
+    #[test]
+    fn graph_remove_node() {
+        // Graph: 0 -> 1 -> 2 -> 3
+        let mut matrix = IncidenceMatrix::from_rows(vec![
+            vec![-1, 1, 0, 0],
+            vec![0, -1, 1, 0],
+            vec![0, 0, -1, 1],
+        ]);
+        matrix.remove_node(1);
+
+        // Current graph: 0    1 -> 2
+        assert_eq!(matrix.order(), 3);
+        assert_eq!(matrix.0, vec![vec![0, -1, 1]]);
+        assert!(matrix.has_edge(1, 2));
+    }
+
+    #[test]
+    fn undirected_graph_add_edge() {
+        // Graph: 0 -> 1    2
+        let mut matrix = IncidenceMatrix::from_rows(vec![vec![-1, 1, 0]]);
+        matrix.add_undirected_edge(0, 1);
+        matrix.add_undirected_edge(1, 2);
+        matrix.add_undirected_edge(2, 1);
+
+        // Current graph: 0 -- 1 -- 2
+        assert_eq!(matrix.undirected_size(), 2);
+        assert_eq!(matrix.0, vec![vec![1, 1, 0], vec![0, 1, 1]]);
+        assert!(matrix.connected());
+    }
+
+    #[test]
+    fn underlying_graph_conversion() {
+        // Graph: 0 -> 1 <- 2    3
+        let matrix = IncidenceMatrix::from_rows(vec![vec![-1, 1, 0, 0], vec![0, 1, -1, 0]]);
+        let underlying = matrix.underlying_graph();
+
+        // Current graph: 0 -- 1 -- 2    3
+        assert_eq!(underlying.order(), 4);
+        assert_eq!(underlying.undirected_size(), 2);
+        assert!(underlying.has_edge(1, 0));
+        assert!(underlying.has_edge(1, 2));
+        assert!(!underlying.connected());
+    }
+
+    #[test]
+    fn dfs_and_biconnected_components() {
+        // 0 -- 1 -- 4
+        //    /  \
+        //   3 -- 2
+        let mut matrix = IncidenceMatrix::new(Vec::new(), 5);
+        matrix.add_undirected_edge(1, 4);
+        matrix.add_undirected_edge(0, 1);
+        matrix.add_undirected_edge(1, 2);
+        matrix.add_undirected_edge(1, 3);
+        matrix.add_undirected_edge(2, 3);
+
+        let discovered = matrix
+            .dfs(0)
+            .filter(|event| matches!(event, crate::DfsEvent::Discover(_, _)))
+            .count();
+        assert_eq!(discovered, 5);
+        assert_eq!(matrix.biconnected_components(0).count(), 3);
+    }
+
+    #[test]
+    fn directed_round_trip_with_adjacency_list() {
+        //    (0)      ->(1)
+        //       \    /   \
+        //        ->(3)     ->(2)
+        //       /           ^  \
+        //     (4)            \_/
+        let adj_list = AdjacencyList(vec![vec![3], vec![2], vec![2], vec![1], vec![3]]);
+
+        let incidence = IncidenceMatrix::from_directed_adjacency_list(&adj_list);
+        let converted = AdjacencyList::from_incidence_matrix(&incidence);
+
+        assert_eq!(incidence.0[2], vec![0, 0, 1, 0, 0]);
+        assert_eq!(adj_list.0, converted.0);
+    }
+
+    #[test]
+    fn directed_round_trip_with_adjacency_matrix() {
+        // Graph: 0 -> 1 -> 2 -> 0, with a loop on 1
+        let matrix = AdjacencyMatrix(vec![vec![0, 1, 0], vec![0, 1, 1], vec![1, 0, 0]]);
+
+        let incidence = IncidenceMatrix::from_adjacency_matrix(&matrix);
+        let converted = AdjacencyMatrix::from_incidency_matrix(&incidence);
+
+        assert_eq!(incidence.size(), 4);
+        assert_eq!(matrix.0, converted.0);
+    }
+
+    #[test]
+    fn undirected_round_trip_with_adjacency_matrix() {
+        // Graph: 0 -- 1 -- 2, with a loop on 2
+        let matrix = AdjacencyMatrix(vec![vec![0, 1, 0], vec![1, 0, 1], vec![0, 1, 1]]);
+
+        let incidence = IncidenceMatrix::from_undirected_adjacency_matrix(&matrix);
+        let converted = AdjacencyMatrix::from_incidency_matrix(&incidence);
+
+        assert_eq!(
+            incidence.0,
+            vec![vec![1, 1, 0], vec![0, 1, 1], vec![0, 0, 1]]
+        );
+        assert_eq!(incidence.undirected_size(), matrix.undirected_size());
+        assert_eq!(matrix.0, converted.0);
+    }
+
+    #[test]
+    fn parallel_edges_to_adjacency_list() {
+        // Graph: 0 == 1 -> 2, where 0 and 1 are joined by two parallel edges
+        let incidence =
+            IncidenceMatrix::from_rows(vec![vec![1, 1, 0], vec![-1, 1, 0], vec![0, -1, 1]]);
+
+        let list = AdjacencyList::from_incidence_matrix(&incidence);
+        let matrix = AdjacencyMatrix::from_incidency_matrix(&incidence);
+
+        assert_eq!(list.0, vec![vec![1], vec![0, 2], vec![]]);
+        assert_eq!(matrix.0, vec![vec![0, 1, 0], vec![1, 0, 1], vec![0, 0, 0]]);
+    }
+
+    #[test]
+    fn undirected_conversion_to_adjacency_list() {
+        // Graph: 0 -- 1 -- 2    3
+        let incidence = IncidenceMatrix::from_rows(vec![vec![1, 1, 0, 0], vec![0, 1, 1, 0]]);
+
+        let list = AdjacencyList::from_incidence_matrix(&incidence);
+
+        assert_eq!(list.order(), 4);
+        assert_eq!(list.undirected_size(), incidence.undirected_size());
+        assert_eq!(list.0, vec![vec![1], vec![0, 2], vec![1], vec![]]);
+    }
 }
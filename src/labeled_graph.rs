@@ -0,0 +1,622 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{BufRead, BufReader, Error, ErrorKind, Write};
+use std::str::FromStr;
+
+use crate::graph_io::UndirectedGraphIO;
+use crate::{Graph, GraphIO, UndirectedGraph};
+
+/// Represents a graph whose nodes are identified by arbitrary keys.
+///
+/// The keys are mapped to the dense indices of an inner graph `G` (like an
+/// [`AdjacencyList`](crate::graphs::AdjacencyList) or an
+/// [`AdjacencyMatrix`](crate::graphs::AdjacencyMatrix)), which stores the edges.
+/// The inner graph must append new nodes at the end and shift the indices
+/// above a removed node down by one, as every representation of this crate does.
+///
+/// The keyed methods (`add_labeled_node`, `add_labeled_edge`, ...) accept any
+/// `K: Eq + Hash + Clone`, like `String`. [`Graph<K>`] is implemented when `K`
+/// is also [`Copy`] (like `&str` or numeric ids), and [`LabeledGraph::borrowed`]
+/// gives a [`Graph<&K>`] for every other key.
+#[derive(Debug, Clone)]
+pub struct LabeledGraph<K, G> {
+    graph: G,
+    labels: Vec<K>,
+    indices: HashMap<K, usize>,
+}
+
+impl<K, G> LabeledGraph<K, G>
+where
+    K: Eq + Hash + Clone,
+    G: Graph<usize>,
+{
+    /// Creates a new and empty labeled graph.
+    pub fn new() -> Self {
+        Self {
+            graph: G::new_empty(),
+            labels: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Returns a reference to the inner graph, indexed by dense node indices.
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /// Returns the index of the node labeled `key` on the inner graph.
+    pub fn index_of(&self, key: &K) -> Option<usize> {
+        self.indices.get(key).copied()
+    }
+
+    /// Returns the label of the node with index `idx` on the inner graph.
+    pub fn label(&self, idx: usize) -> Option<&K> {
+        self.labels.get(idx)
+    }
+
+    /// Returns an iterator over all labels, in the order of their indices.
+    pub fn labels(&self) -> impl Iterator<Item = &K> {
+        self.labels.iter()
+    }
+
+    /// Adds a node labeled `key` and returns its index.
+    ///
+    /// If the label already exists, its current index is returned.
+    pub fn add_labeled_node(&mut self, key: K) -> usize {
+        if let Some(&idx) = self.indices.get(&key) {
+            return idx;
+        }
+
+        let idx = self.graph.order();
+        self.graph.add_node(idx);
+        self.indices.insert(key.clone(), idx);
+        self.labels.push(key);
+        idx
+    }
+
+    /// Removes the node labeled `key` and all edges connected to it.
+    ///
+    /// If the label does not exist, this operation has no effect.
+    pub fn remove_labeled_node(&mut self, key: &K) {
+        if let Some(idx) = self.indices.remove(key) {
+            self.graph.remove_node(idx);
+            self.labels.remove(idx);
+            for label in &self.labels[idx..] {
+                if let Some(i) = self.indices.get_mut(label) {
+                    *i -= 1;
+                }
+            }
+        }
+    }
+
+    /// Adds a directed edge between the nodes labeled `n` and `m`.
+    ///
+    /// If either label does not exist, this operation has no effect.
+    pub fn add_labeled_edge(&mut self, n: &K, m: &K) {
+        if let (Some(n), Some(m)) = (self.index_of(n), self.index_of(m)) {
+            self.graph.add_edge(n, m);
+        }
+    }
+
+    /// Removes the directed edge between the nodes labeled `n` and `m`, if it exists.
+    pub fn remove_labeled_edge(&mut self, n: &K, m: &K) {
+        if let (Some(n), Some(m)) = (self.index_of(n), self.index_of(m)) {
+            self.graph.remove_edge(n, m);
+        }
+    }
+
+    /// Returns a copy of the graph keyed by references to its keys.
+    ///
+    /// References are [`Copy`] whatever `K` is, so the result implements [`Graph<&K>`]
+    /// and every generic algorithm runs on it, reporting each node by its key.
+    pub fn borrowed(&self) -> LabeledGraph<&K, G>
+    where
+        G: Clone,
+    {
+        LabeledGraph {
+            graph: self.graph.clone(),
+            labels: self.labels.iter().collect(),
+            indices: self
+                .labels
+                .iter()
+                .enumerate()
+                .map(|(i, k)| (k, i))
+                .collect(),
+        }
+    }
+
+    /// Returns an iterator over the labels of the neighbors of the node labeled `n`.
+    pub fn labeled_neighbors<'a>(&'a self, n: &K) -> impl Iterator<Item = &'a K> + 'a {
+        self.index_of(n)
+            .into_iter()
+            .flat_map(|idx| self.graph.neighbors(idx))
+            .map(|idx| &self.labels[idx])
+    }
+
+    /// Imports a labeled graph from a file.
+    ///
+    /// The first line lists the labels of every node separated by commas, and each
+    /// following line holds an edge `label,label`. Labels are parsed with [`FromStr`].
+    ///
+    /// # Arguments
+    /// * `path` - The path to the input file containing the graph.
+    pub fn import_labeled_from_file(path: String) -> Result<Self, Error>
+    where
+        K: FromStr,
+    {
+        let mut graph = Self::new();
+        for_each_labeled_line(path, &mut graph, |graph, n, m| {
+            graph.add_labeled_edge(&n, &m)
+        })?;
+        Ok(graph)
+    }
+
+    /// Exports the graph to a DOT file, writing each node by its label.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the output DOT file. If it's inside a folder, this folder must exists earlier.
+    pub fn export_labeled_to_dot(&self, mut path: String) -> Result<(), Error>
+    where
+        K: Display,
+    {
+        if !path.contains(".dot") {
+            path += ".dot";
+        }
+
+        let mut file: File = File::create(path)?;
+
+        writeln!(file, "digraph G {{")?;
+        writeln!(file, "  rankdir=LR;")?;
+        writeln!(file, "  node [shape=circle];")?;
+
+        for (idx, label) in self.labels.iter().enumerate() {
+            writeln!(file, " {} ", quoted(label))?;
+            for neighbor in self.graph.neighbors(idx) {
+                writeln!(
+                    file,
+                    " {} -> {} ",
+                    quoted(label),
+                    quoted(&self.labels[neighbor])
+                )?;
+            }
+        }
+
+        writeln!(file, " }}")?;
+
+        Ok(())
+    }
+}
+
+impl<K, G> LabeledGraph<K, G>
+where
+    K: Eq + Hash + Clone,
+    G: UndirectedGraph<usize>,
+{
+    /// Adds an undirected edge between the nodes labeled `n` and `m`.
+    ///
+    /// If either label does not exist, this operation has no effect.
+    pub fn add_labeled_undirected_edge(&mut self, n: &K, m: &K) {
+        if let (Some(n), Some(m)) = (self.index_of(n), self.index_of(m)) {
+            self.graph.add_undirected_edge(n, m);
+        }
+    }
+
+    /// Imports an undirected labeled graph from a file.
+    ///
+    /// The file layout is the same as [`LabeledGraph::import_labeled_from_file`].
+    ///
+    /// # Arguments
+    /// * `path` - The path to the input file containing the undirected graph.
+    pub fn import_labeled_undirected_from_file(path: String) -> Result<Self, Error>
+    where
+        K: FromStr,
+    {
+        let mut graph = Self::new();
+        for_each_labeled_line(path, &mut graph, |graph, n, m| {
+            graph.add_labeled_undirected_edge(&n, &m)
+        })?;
+        Ok(graph)
+    }
+
+    /// Exports an undirected labeled graph to a DOT file, writing each node by its label.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the output DOT file. If it's inside a folder, this folder must exists earlier.
+    pub fn export_labeled_undirected_to_dot(&self, mut path: String) -> Result<(), Error>
+    where
+        K: Display,
+    {
+        if !path.contains(".dot") {
+            path += ".dot";
+        }
+
+        let mut file: File = File::create(&path)?;
+
+        writeln!(file, "graph G {{")?;
+        writeln!(file, "  rankdir=LR;")?;
+        writeln!(file, "  node [shape=circle];")?;
+
+        for (idx, label) in self.labels.iter().enumerate() {
+            writeln!(file, " {} ", quoted(label))?;
+            for neighbor in self
+                .graph
+                .neighbors(idx)
+                .filter(|&neighbor| neighbor >= idx)
+            {
+                writeln!(
+                    file,
+                    " {} -- {} ",
+                    quoted(label),
+                    quoted(&self.labels[neighbor])
+                )?;
+            }
+        }
+
+        writeln!(file, " }}")?;
+
+        Ok(())
+    }
+}
+
+impl<K, G> Default for LabeledGraph<K, G>
+where
+    K: Eq + Hash + Clone,
+    G: Graph<usize>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads a labeled graph file, adding its nodes to `graph` and calling `add_edge` for every edge line.
+fn for_each_labeled_line<K, G>(
+    path: String,
+    graph: &mut LabeledGraph<K, G>,
+    mut add_edge: impl FnMut(&mut LabeledGraph<K, G>, K, K),
+) -> Result<(), Error>
+where
+    K: Eq + Hash + Clone + FromStr,
+    G: Graph<usize>,
+{
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+
+    let parse = |x: &str| {
+        x.trim().parse::<K>().map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid label was found during file creation: {} ", x),
+            )
+        })
+    };
+
+    for (idx, line) in reader.lines().enumerate() {
+        let content = line?;
+        if content.is_empty() {
+            continue;
+        }
+
+        if idx == 0 {
+            for label in content.split(',') {
+                graph.add_labeled_node(parse(label)?);
+            }
+        } else {
+            let values: Vec<K> = content
+                .split(',')
+                .map(parse)
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            match values.as_slice() {
+                [n, m] if graph.index_of(n).is_some() && graph.index_of(m).is_some() => {
+                    add_edge(graph, n.clone(), m.clone())
+                }
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Invalid edge was found during file creation: {} ", content),
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Quotes a label so it can be used as a DOT identifier.
+fn quoted(label: &impl Display) -> String {
+    format!("\"{}\"", label.to_string().replace('"', "\\\""))
+}
+
+impl<K, G> Graph<K> for LabeledGraph<K, G>
+where
+    K: Eq + Hash + Copy,
+    G: Graph<usize>,
+{
+    fn new_empty() -> Self {
+        Self::new()
+    }
+
+    fn order(&self) -> usize {
+        self.graph.order()
+    }
+
+    fn size(&self) -> usize {
+        self.graph.size()
+    }
+
+    fn node_degrees(&self, n: K) -> (usize, usize) {
+        self.index_of(&n)
+            .map_or((0, 0), |idx| self.graph.node_degrees(idx))
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = K> {
+        self.labels.iter().copied()
+    }
+
+    fn add_node(&mut self, n: K) {
+        self.add_labeled_node(n);
+    }
+
+    fn remove_node(&mut self, n: K) {
+        self.remove_labeled_node(&n);
+    }
+
+    fn add_edge(&mut self, n: K, m: K) {
+        self.add_labeled_edge(&n, &m);
+    }
+
+    fn remove_edge(&mut self, n: K, m: K) {
+        self.remove_labeled_edge(&n, &m);
+    }
+
+    type Neighbors<'a>
+        = LabeledNeighbors<'a, K, G>
+    where
+        Self: 'a,
+        K: 'a;
+
+    fn neighbors<'a>(&'a self, n: K) -> Self::Neighbors<'a> {
+        LabeledNeighbors {
+            inner: self.index_of(&n).map(|idx| self.graph.neighbors(idx)),
+            labels: &self.labels,
+        }
+    }
+
+    fn underlying_graph(&self) -> Self {
+        Self {
+            graph: self.graph.underlying_graph(),
+            labels: self.labels.clone(),
+            indices: self.indices.clone(),
+        }
+    }
+}
+
+/// Iterator over the neighbors of a node in a [`LabeledGraph`].
+///
+/// It maps the indices yielded by the inner graph back to their labels.
+pub struct LabeledNeighbors<'a, K, G>
+where
+    G: Graph<usize> + 'a,
+{
+    inner: Option<G::Neighbors<'a>>,
+    labels: &'a [K],
+}
+
+impl<'a, K, G> Iterator for LabeledNeighbors<'a, K, G>
+where
+    K: Copy,
+    G: Graph<usize> + 'a,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.as_mut()?.next().map(|idx| self.labels[idx])
+    }
+}
+
+impl<K, G> UndirectedGraph<K> for LabeledGraph<K, G>
+where
+    K: Eq + Hash + Copy,
+    G: UndirectedGraph<usize>,
+{
+    fn undirected_size(&self) -> usize {
+        self.graph.undirected_size()
+    }
+
+    fn add_undirected_edge(&mut self, n: K, m: K) {
+        self.add_labeled_undirected_edge(&n, &m);
+    }
+
+    fn undirected_node_degree(&self, n: K) -> usize {
+        self.index_of(&n)
+            .map_or(0, |idx| self.graph.undirected_node_degree(idx))
+    }
+}
+
+impl<K, G> GraphIO<K> for LabeledGraph<K, G>
+where
+    K: Eq + Hash + Copy + Display,
+    G: Graph<usize>,
+{
+}
+
+impl<K, G> UndirectedGraphIO<K> for LabeledGraph<K, G>
+where
+    K: Eq + Hash + Copy + Display,
+    G: UndirectedGraph<usize>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+
+    use super::*;
+    use crate::graphs::{AdjacencyList, AdjacencyMatrix};
+
+    static PATH: &str = "examples/data/";
+
+    #[test]
+    fn labeled_nodes_and_edges() {
+        // Graph: "a" -> "b" -> "c"
+        let mut graph: LabeledGraph<String, AdjacencyList> = LabeledGraph::new();
+        let a = graph.add_labeled_node("a".to_string());
+        let b = graph.add_labeled_node("b".to_string());
+        graph.add_labeled_node("c".to_string());
+        graph.add_labeled_edge(&"a".to_string(), &"b".to_string());
+        graph.add_labeled_edge(&"b".to_string(), &"c".to_string());
+
+        assert_eq!(graph.add_labeled_node("a".to_string()), a);
+        assert_eq!(graph.index_of(&"b".to_string()), Some(b));
+        assert_eq!(graph.inner().0, vec![vec![1], vec![2], vec![]]);
+        assert_eq!(
+            graph
+                .labeled_neighbors(&"b".to_string())
+                .collect::<Vec<_>>(),
+            vec!["c"]
+        );
+    }
+
+    #[test]
+    fn remove_labeled_node_keeps_labels_consistent() {
+        // Graph: 10 -> 20 -> 30 -> 10
+        let mut graph: LabeledGraph<u64, AdjacencyMatrix> = LabeledGraph::new();
+        graph.add_node(10);
+        graph.add_node(20);
+        graph.add_node(30);
+        graph.add_edge(10, 20);
+        graph.add_edge(20, 30);
+        graph.add_edge(30, 10);
+
+        graph.remove_node(20);
+
+        // Current graph: 10 <- 30
+        assert_eq!(graph.order(), 2);
+        assert_eq!(graph.nodes().collect::<Vec<_>>(), vec![10, 30]);
+        assert_eq!(graph.index_of(&30), Some(1));
+        assert!(graph.has_edge(30, 10));
+        assert!(!graph.has_edge(10, 30));
+        assert_eq!(graph.node_degrees(10), (1, 0));
+    }
+
+    #[test]
+    fn generic_algorithms_over_labels() {
+        // Graph: "x" -- "y" -- "z"
+        let mut graph: LabeledGraph<&str, AdjacencyList> = LabeledGraph::new();
+        graph.add_node("x");
+        graph.add_node("y");
+        graph.add_node("z");
+        graph.add_undirected_edge("x", "y");
+        graph.add_undirected_edge("y", "z");
+
+        let discovered: Vec<&str> = graph
+            .dfs("x")
+            .filter_map(|event| match event {
+                crate::DfsEvent::Discover(node, _) => Some(node),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(discovered, vec!["x", "y", "z"]);
+        assert_eq!(graph.undirected_size(), 2);
+        assert!(graph.connected());
+        assert_eq!(graph.biconnected_components("x").count(), 2);
+    }
+
+    #[test]
+    fn import_labeled_graph() {
+        let result: Result<LabeledGraph<String, AdjacencyList>, Error> =
+            LabeledGraph::import_labeled_undirected_from_file(
+                PATH.to_owned() + "GRAFO_ROTULADO.txt",
+            );
+
+        assert!(result.is_ok());
+
+        if let Ok(graph) = result {
+            assert_eq!(graph.inner().order(), 8);
+            assert_eq!(graph.inner().undirected_size(), 9);
+            assert_eq!(graph.labeled_neighbors(&"f".to_string()).count(), 3);
+        }
+    }
+
+    #[test]
+    fn import_labeled_graph_with_unknown_label() {
+        // The first line of this file holds a node count instead of labels.
+        let result: Result<LabeledGraph<String, AdjacencyList>, Error> =
+            LabeledGraph::import_labeled_from_file(PATH.to_owned() + "GRAFO_0.txt");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn import_labeled_graph_with_extra_column() {
+        let name = format!("labeled_graph_extra_column_{}.txt", std::process::id());
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, "a,b,c\na,b\nb,c,a\n").unwrap();
+
+        let result: Result<LabeledGraph<String, AdjacencyList>, Error> =
+            LabeledGraph::import_labeled_from_file(path.display().to_string());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+
+        if let Err(error) = result {
+            assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn generic_algorithms_over_string_keys() {
+        // Graph: "Lisbon" -- "Madrid" -- "Paris"    "Oslo"
+        let mut graph: LabeledGraph<String, AdjacencyList> = LabeledGraph::new();
+        let [lisbon, madrid, paris, oslo] =
+            ["Lisbon", "Madrid", "Paris", "Oslo"].map(|city| city.to_string());
+        for city in [&lisbon, &madrid, &paris, &oslo] {
+            graph.add_labeled_node(city.clone());
+        }
+        graph.add_labeled_undirected_edge(&lisbon, &madrid);
+        graph.add_labeled_undirected_edge(&madrid, &paris);
+
+        let keyed = graph.borrowed();
+
+        let discovered: Vec<&String> = keyed
+            .dfs(&madrid)
+            .filter_map(|event| match event {
+                crate::DfsEvent::Discover(node, _) => Some(node),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(discovered, vec!["Madrid", "Lisbon", "Paris"]);
+        assert_eq!(
+            keyed.shortest_path(&lisbon, &paris),
+            Some(vec![&lisbon, &madrid, &paris])
+        );
+        assert!(!keyed.connected());
+        assert_eq!(keyed.articulation_points(), vec![&madrid]);
+    }
+
+    #[test]
+    fn export_labeled_graph() {
+        let name = format!("labeled_graph_export_{}.dot", std::process::id());
+        let path = std::env::temp_dir().join(name);
+        let mut graph: LabeledGraph<String, AdjacencyList> = LabeledGraph::new();
+        graph.add_labeled_node("New York".to_string());
+        graph.add_labeled_node("Boston".to_string());
+        graph.add_labeled_edge(&"New York".to_string(), &"Boston".to_string());
+
+        assert!(
+            graph
+                .export_labeled_to_dot(path.display().to_string())
+                .is_ok()
+        );
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(content.contains("\"New York\" -> \"Boston\""));
+    }
+}
@@ -10,28 +10,98 @@
 //! - `adjacency_list`: Implementation of graphs using adjacency lists.
 //! - `adjacency_matrix`: Implementation of graphs using adjacency matrices.
 //! - `incidence_matrix`: Implementation of graphs using incidence matrices.
+//! - `labeled_graph`: Graphs whose nodes are identified by arbitrary keys.
+//! - `weighted_graph`: Core traits for graphs with weighted edges.
+//! - `weighted_adjacency_list`: Implementation of weighted graphs using adjacency lists.
+//! - `shortest_paths`: Shortest path algorithms over weighted graphs.
+//! - `all_pairs`: All-pairs shortest paths and transitive closure.
+//! - `flow`: Maximum flows and minimum cuts of capacity-weighted digraphs.
+//! - `min_cost_flow`: Minimum cost maximum flows of digraphs with capacities and costs.
+//! - `matching`: Maximum matchings, vertex covers and independent sets.
+//! - `blossom`: Maximum and maximum weight matchings of general graphs.
+//! - `assignment`: Optimal assignments over dense cost matrices.
+//! - `mst`: Minimum spanning forests of weighted undirected graphs.
+//! - `bipartite`: Bipartitions of graphs, with odd cycles as certificates.
+//! - `block_cut_tree`: Block-cut trees of undirected graphs.
+//! - `components`: Connected and weakly connected components.
+//! - `scc`: Strongly connected components and condensation of digraphs.
+//! - `topological`: Topological sorting of digraphs.
+//! - `union_find`: Disjoint-set forests and incremental connectivity.
+//! - `visitor`: Traversals driven by visitor hooks, with pruning and early termination.
 //! - `graph_io`: Traits for importing/exporting graphs.
 //! - `utils`: Helper functions for printing things on the shell.
 mod adjacency_list;
 mod adjacency_matrix;
+mod all_pairs;
+mod assignment;
+mod bipartite;
+mod block_cut_tree;
+mod blossom;
+mod components;
+mod flow;
 mod graph;
 mod graph_io;
 mod incidence_matrix;
+mod labeled_graph;
+mod matching;
+mod min_cost_flow;
+mod mst;
+mod scc;
+mod shortest_paths;
+#[cfg(test)]
+mod test_rng;
+mod topological;
+mod union_find;
 pub mod utils;
+mod visitor;
+mod weighted_adjacency_list;
+mod weighted_graph;
 
+pub use all_pairs::AllPairsShortestPaths;
+pub use assignment::Assignment;
+pub use bipartite::Bipartition;
+pub use bipartite::OddCycle;
+pub use block_cut_tree::BlockCutNode;
+pub use block_cut_tree::BlockCutTree;
+pub use components::Components;
+pub use flow::MaxFlow;
+pub use flow::ResidualGraph;
 pub use graph::BfsEvent;
+pub use graph::BfsTree;
 pub use graph::DfsEvent;
+pub use graph::DfsTree;
 pub use graph::Edge;
+pub use graph::ForestEvent;
 pub use graph::Graph;
 pub use graph::UndirectedGraph;
 pub use graph_io::GraphIO;
 pub use graph_io::UndirectedGraphIO;
+pub use graph_io::WeightedGraphIO;
+pub use matching::BipartiteMatching;
+pub use matching::Matching;
+pub use min_cost_flow::MinCostFlow;
+pub use mst::SpanningForest;
+pub use scc::KosarajuSccIter;
+pub use scc::TarjanSccIter;
+pub use shortest_paths::AStarSearch;
+pub use shortest_paths::NegativeCycle;
+pub use shortest_paths::ShortestPaths;
+pub use topological::AllTopologicalOrders;
+pub use topological::Cycle;
+pub use union_find::IncrementalConnectivity;
+pub use union_find::RollbackUnionFind;
+pub use union_find::UnionFind;
 pub use utils::print_list;
 pub use utils::print_matrix;
+pub use visitor::Control;
+pub use visitor::Visitor;
+pub use weighted_graph::Weight;
+pub use weighted_graph::WeightedGraph;
 
 pub mod graphs {
     pub use crate::{
         adjacency_list::AdjacencyList, adjacency_matrix::AdjacencyMatrix,
-        incidence_matrix::IncidenceMatrix,
+        incidence_matrix::IncidenceMatrix, labeled_graph::LabeledGraph,
+        weighted_adjacency_list::WeightedAdjacencyList,
     };
 }
@@ -0,0 +1,400 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::Graph;
+use crate::bipartite::OddCycle;
+use crate::components::predecessors;
+
+/// Represents a **matching**: a set of edges without common nodes.
+#[derive(Debug, Clone)]
+pub struct Matching<Node> {
+    edges: Vec<(Node, Node)>,
+    mate: HashMap<Node, Node>,
+}
+
+impl<Node: Eq + Hash + Copy> Matching<Node> {
+    /// Creates a matching from its edges, which must not share any node.
+    pub(crate) fn new(edges: Vec<(Node, Node)>) -> Self {
+        let mate = edges.iter().flat_map(|&(u, v)| [(u, v), (v, u)]).collect();
+        Self { edges, mate }
+    }
+
+    /// Returns the number of edges on the matching.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns `true` if the matching has no edges.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Returns the edges of the matching.
+    pub fn edges(&self) -> &[(Node, Node)] {
+        &self.edges
+    }
+
+    /// Returns the node matched to `node`, or `None` if it's unmatched.
+    pub fn mate(&self, node: Node) -> Option<Node> {
+        self.mate.get(&node).copied()
+    }
+
+    /// Returns `true` if `node` is covered by an edge of the matching.
+    pub fn is_matched(&self, node: Node) -> bool {
+        self.mate.contains_key(&node)
+    }
+}
+
+/// Represents a **maximum matching** of a bipartite graph, together with the
+/// **minimum vertex cover** and the **maximum independent set** derived from it.
+///
+/// By Kőnig's theorem, the vertex cover has as many nodes as the matching has edges,
+/// and the independent set is made of every node out of the cover.
+#[derive(Debug, Clone)]
+pub struct BipartiteMatching<Node> {
+    matching: Matching<Node>,
+    vertex_cover: Vec<Node>,
+    independent_set: Vec<Node>,
+}
+
+impl<Node: Eq + Hash + Copy> BipartiteMatching<Node> {
+    /// Returns the maximum matching. Each edge is given from its left end to its right end.
+    pub fn matching(&self) -> &Matching<Node> {
+        &self.matching
+    }
+
+    /// Returns a minimum set of nodes that touches every edge, in [`Graph::nodes`] order.
+    pub fn vertex_cover(&self) -> &[Node] {
+        &self.vertex_cover
+    }
+
+    /// Returns a maximum set of nodes without edges between them, in [`Graph::nodes`] order.
+    pub fn independent_set(&self) -> &[Node] {
+        &self.independent_set
+    }
+}
+
+/// The state of the Hopcroft-Karp algorithm, with the left nodes numbered `0..l`
+/// and the right nodes numbered `0..r`.
+struct HopcroftKarp {
+    adjacent: Vec<Vec<usize>>,
+    left_mate: Vec<Option<usize>>,
+    right_mate: Vec<Option<usize>>,
+    layer: Vec<usize>,
+    /// The layer of the left nodes that reach a free right node on the current phase.
+    free_layer: usize,
+    /// The first edge of each left node that may still extend a path on the current phase.
+    next: Vec<usize>,
+}
+
+impl HopcroftKarp {
+    fn new(adjacent: Vec<Vec<usize>>, right: usize) -> Self {
+        let left = adjacent.len();
+        Self {
+            adjacent,
+            left_mate: vec![None; left],
+            right_mate: vec![None; right],
+            layer: vec![usize::MAX; left],
+            free_layer: usize::MAX,
+            next: vec![0; left],
+        }
+    }
+
+    /// Layers the left nodes by BFS from the free ones, alternating between free edges
+    /// and matched edges, and stops past the first layer that reaches a free right node.
+    /// Returns `true` if a free right node was reached.
+    fn layer_from_free_nodes(&mut self) -> bool {
+        let mut queue: VecDeque<usize> = VecDeque::new();
+        for u in 0..self.adjacent.len() {
+            self.next[u] = 0;
+            if self.left_mate[u].is_none() {
+                self.layer[u] = 0;
+                queue.push_back(u);
+            } else {
+                self.layer[u] = usize::MAX;
+            }
+        }
+
+        self.free_layer = usize::MAX;
+        while let Some(u) = queue.pop_front() {
+            if self.layer[u] >= self.free_layer {
+                break;
+            }
+            for &v in &self.adjacent[u] {
+                match self.right_mate[v] {
+                    None => self.free_layer = self.layer[u],
+                    Some(w) if self.layer[w] == usize::MAX => {
+                        self.layer[w] = self.layer[u] + 1;
+                        queue.push_back(w);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        self.free_layer != usize::MAX
+    }
+
+    /// Returns `true` if the edge from the left node `u` to the right node `v`
+    /// extends a shortest augmenting path.
+    fn extends(&self, u: usize, v: usize) -> bool {
+        match self.right_mate[v] {
+            None => self.layer[u] == self.free_layer,
+            Some(w) => self.layer[w] == self.layer[u] + 1,
+        }
+    }
+
+    /// Searches a shortest augmenting path from the free left node `root` along the
+    /// layers, and flips it if there's one.
+    ///
+    /// The path is walked with an explicit stack, so long paths can't overflow
+    /// the call stack, and every dead end is skipped on later searches of the phase.
+    fn augment(&mut self, root: usize) -> bool {
+        let mut path: Vec<(usize, usize)> = Vec::new();
+        let mut u = root;
+
+        loop {
+            let edge = (self.next[u]..self.adjacent[u].len())
+                .find(|&i| self.extends(u, self.adjacent[u][i]));
+
+            match edge {
+                Some(i) => {
+                    self.next[u] = i;
+                    path.push((u, i));
+                    match self.right_mate[self.adjacent[u][i]] {
+                        None => break,
+                        Some(w) => u = w,
+                    }
+                }
+                None => {
+                    // A dead end: step back and skip the edge that led here.
+                    self.layer[u] = usize::MAX;
+                    self.next[u] = self.adjacent[u].len();
+                    let Some((prev, i)) = path.pop() else {
+                        return false;
+                    };
+                    self.next[prev] = i + 1;
+                    u = prev;
+                }
+            }
+        }
+
+        for (u, i) in path {
+            let v = self.adjacent[u][i];
+            self.left_mate[u] = Some(v);
+            self.right_mate[v] = Some(u);
+        }
+        true
+    }
+
+    /// Grows the matching by a maximal set of shortest augmenting paths on every phase,
+    /// which takes `O(√n)` phases.
+    fn run(&mut self) {
+        while self.layer_from_free_nodes() {
+            for u in 0..self.adjacent.len() {
+                if self.left_mate[u].is_none() {
+                    self.augment(u);
+                }
+            }
+        }
+    }
+
+    /// Returns the nodes reached from the free left nodes through alternating paths,
+    /// as flags over the left nodes and over the right nodes.
+    fn alternating_reach(&self) -> (Vec<bool>, Vec<bool>) {
+        let mut left_reached = vec![false; self.adjacent.len()];
+        let mut right_reached = vec![false; self.right_mate.len()];
+        let mut stack: Vec<usize> = (0..self.adjacent.len())
+            .filter(|&u| self.left_mate[u].is_none())
+            .collect();
+        for &u in &stack {
+            left_reached[u] = true;
+        }
+
+        while let Some(u) = stack.pop() {
+            for &v in &self.adjacent[u] {
+                if right_reached[v] {
+                    continue;
+                }
+                right_reached[v] = true;
+                if let Some(w) = self.right_mate[v]
+                    && !left_reached[w]
+                {
+                    left_reached[w] = true;
+                    stack.push(w);
+                }
+            }
+        }
+        (left_reached, right_reached)
+    }
+}
+
+/// Computes a maximum matching of `graph` with the **Hopcroft-Karp algorithm**,
+/// ignoring the direction of edges.
+///
+/// The vertex cover is built with Kőnig's theorem: out of the nodes reached from the free
+/// left nodes through alternating paths, it takes the right ones, plus the left nodes
+/// that weren't reached.
+pub(crate) fn hopcroft_karp<Node, G>(graph: &G) -> Result<BipartiteMatching<Node>, OddCycle<Node>>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    let sides = graph.bipartition()?;
+    let (left, right) = (sides.left(), sides.right());
+    let right_index: HashMap<Node, usize> =
+        right.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+
+    let predecessors = predecessors(graph);
+    let adjacent: Vec<Vec<usize>> = left
+        .iter()
+        .map(|&u| {
+            graph
+                .neighbors(u)
+                .chain(predecessors.get(&u).into_iter().flatten().copied())
+                .map(|v| right_index[&v])
+                .collect()
+        })
+        .collect();
+
+    let mut search = HopcroftKarp::new(adjacent, right.len());
+    search.run();
+
+    let edges: Vec<(Node, Node)> = left
+        .iter()
+        .zip(&search.left_mate)
+        .filter_map(|(&u, mate)| mate.map(|v| (u, right[v])))
+        .collect();
+
+    let (left_reached, right_reached) = search.alternating_reach();
+    let in_cover: HashSet<Node> = left
+        .iter()
+        .zip(left_reached)
+        .filter_map(|(&u, reached)| (!reached).then_some(u))
+        .chain(
+            right
+                .iter()
+                .zip(right_reached)
+                .filter_map(|(&v, reached)| reached.then_some(v)),
+        )
+        .collect();
+    let (vertex_cover, independent_set) = graph.nodes().partition(|n| in_cover.contains(n));
+    Ok(BipartiteMatching {
+        matching: Matching::new(edges),
+        vertex_cover,
+        independent_set,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphs::{AdjacencyList, AdjacencyMatrix};
+    use crate::{Graph, UndirectedGraph};
+
+    /// Builds a graph of workers `0..5` and shifts `5..10`, with an edge
+    /// for every shift each worker can take.
+    fn shifts() -> AdjacencyList {
+        let mut graph = AdjacencyList(vec![vec![]; 10]);
+        for (worker, shift) in [
+            (0, 5),
+            (0, 6),
+            (1, 5),
+            (2, 6),
+            (2, 7),
+            (2, 8),
+            (3, 6),
+            (4, 8),
+            (4, 9),
+        ] {
+            graph.add_undirected_edge(worker, shift);
+        }
+        graph
+    }
+
+    #[test]
+    fn maximum_matching_and_konig() {
+        let graph = shifts();
+
+        let result = graph.hopcroft_karp();
+
+        assert!(result.is_ok());
+
+        if let Ok(result) = result {
+            let matching = result.matching();
+            // Workers 0, 1 and 3 only fit on shifts 5 and 6.
+            assert_eq!(matching.len(), 4);
+            for &(u, v) in matching.edges() {
+                assert!(graph.has_edge(u, v));
+                assert_eq!(matching.mate(v), Some(u));
+            }
+
+            let cover = result.vertex_cover();
+            assert_eq!(cover.len(), matching.len());
+            for u in graph.nodes() {
+                assert!(
+                    graph
+                        .neighbors(u)
+                        .all(|v| cover.contains(&u) || cover.contains(&v))
+                );
+            }
+
+            let independent = result.independent_set();
+            assert_eq!(independent.len(), graph.order() - matching.len());
+            for &u in independent {
+                assert!(graph.neighbors(u).all(|v| !independent.contains(&v)));
+            }
+        }
+    }
+
+    #[test]
+    fn perfect_matching_on_digraph() {
+        // 0 -> 1, 2 -> 1, 2 -> 3, 0 -> 3 and isolated 4 and 5
+        let matrix = AdjacencyMatrix(vec![
+            vec![0, 1, 0, 1, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 1, 0, 1, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+        ]);
+
+        let result = matrix.hopcroft_karp();
+
+        assert!(result.is_ok());
+
+        if let Ok(result) = result {
+            assert_eq!(result.matching().len(), 2);
+            assert!(!result.matching().is_matched(4));
+            assert_eq!(result.vertex_cover().len(), 2);
+            assert_eq!(result.independent_set().len(), 4);
+        }
+    }
+
+    #[test]
+    fn odd_cycle_has_no_bipartite_matching() {
+        let mut triangle = AdjacencyList(vec![vec![]; 3]);
+        triangle.add_undirected_edge(0, 1);
+        triangle.add_undirected_edge(1, 2);
+        triangle.add_undirected_edge(2, 0);
+
+        assert!(triangle.hopcroft_karp().is_err());
+    }
+
+    #[test]
+    fn long_augmenting_path() {
+        // Left nodes 0..=k and right nodes k+1..=2k+1, where left node i prefers
+        // right node i + 1. The first phase leaves k and 0 free on each side, so the
+        // second one has to flip a path through every node.
+        let k = 50_000;
+        let right = |i: usize| k + 1 + i;
+        let mut graph = AdjacencyList(vec![vec![]; 2 * k + 2]);
+        for i in 0..k {
+            graph.0[i] = vec![right(i + 1), right(i)];
+        }
+        graph.0[k] = vec![right(k)];
+
+        let result = graph.hopcroft_karp().expect("the graph is bipartite");
+
+        assert_eq!(result.matching().len(), k + 1);
+        assert_eq!(result.matching().mate(0), Some(right(0)));
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::{Mul, Neg};
+
+use crate::flow::{self, FlowNetwork, ResidualGraph};
+use crate::shortest_paths::MinScored;
+use crate::weighted_graph::{Weight, WeightedGraph};
+
+/// Represents a **minimum cost maximum flow** from a source to a sink: among every
+/// maximum flow, one whose total cost is the least.
+///
+/// Each edge `(u -> v)` has its weight as capacity, of type `W`, and a cost of type `C`
+/// for each unit of flow it takes.
+#[derive(Debug, Clone)]
+pub struct MinCostFlow<Node, W, C> {
+    value: W,
+    cost: C,
+    flows: HashMap<(Node, Node), W>,
+    residual: ResidualGraph<W>,
+}
+
+impl<Node, W, C> MinCostFlow<Node, W, C>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    C: Weight,
+{
+    /// Returns the value of the flow: the net amount that leaves the source.
+    pub fn value(&self) -> W {
+        self.value
+    }
+
+    /// Returns the total cost of the flow: the flow through each edge times its cost.
+    pub fn cost(&self) -> C {
+        self.cost
+    }
+
+    /// Returns the flow through the edge `(u -> v)`, or zero if there's no such edge.
+    pub fn flow(&self, u: Node, v: Node) -> W {
+        self.flows.get(&(u, v)).copied().unwrap_or(W::zero())
+    }
+
+    /// Returns the flow through every edge of the network.
+    pub fn flows(&self) -> &HashMap<(Node, Node), W> {
+        &self.flows
+    }
+
+    /// Returns the residual graph left by the flow, whose node `i` is the `i`-th node
+    /// on [`Graph::nodes`](crate::Graph::nodes) of the network.
+    pub fn residual(&self) -> &ResidualGraph<W> {
+        &self.residual
+    }
+}
+
+/// A flow network together with the cost of each arc on its residual graph.
+/// Reverse arcs cost the opposite of their edge, as pushing flow back refunds it.
+struct CostNetwork<Node, W, C> {
+    network: FlowNetwork<Node, W>,
+    cost: Vec<Vec<C>>,
+}
+
+impl<Node, W, C> CostNetwork<Node, W, C>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    C: Weight + Mul<Output = C> + Neg<Output = C> + TryFrom<W>,
+{
+    /// Builds the network, or returns `None` if `source` and `sink` aren't two distinct
+    /// nodes of `graph`, or if some edge of `graph` is missing from `cost`.
+    fn new<G: WeightedGraph<Node, W>>(
+        graph: &G,
+        source: Node,
+        sink: Node,
+        cost: &HashMap<(Node, Node), C>,
+    ) -> Option<Self> {
+        let network = FlowNetwork::new(graph, source, sink)?;
+        let residual = &network.residual;
+
+        let mut costs: Vec<Vec<C>> = residual
+            .arcs
+            .0
+            .iter()
+            .map(|arcs| vec![C::zero(); arcs.len()])
+            .collect();
+        for &(u, k, _) in &network.edges {
+            let v = residual.arcs.0[u][k];
+            let c = *cost.get(&(network.nodes[u], network.nodes[v]))?;
+            costs[u][k] = c;
+            costs[v][residual.twin(u, k)] = -c;
+        }
+
+        Some(Self {
+            network,
+            cost: costs,
+        })
+    }
+
+    /// Computes potentials under which no arc with residual capacity left has
+    /// a negative reduced cost, with the Bellman-Ford algorithm from a virtual node
+    /// linked to every node.
+    ///
+    /// If there's none, returns the arcs of a cycle with negative total cost instead,
+    /// as `(u, k)` for the `k`-th arc leaving `u`.
+    fn potentials(&self) -> Result<Vec<C>, Vec<(usize, usize)>> {
+        let residual = &self.network.residual;
+        let n = residual.arcs.0.len();
+        let mut distance = vec![C::zero(); n];
+        let mut parent: Vec<Option<(usize, usize)>> = vec![None; n];
+
+        let mut last = None;
+        for _ in 0..n {
+            last = None;
+            for u in 0..n {
+                for (k, v) in residual.open_arcs(u) {
+                    let candidate = distance[u] + self.cost[u][k];
+                    if candidate < distance[v] {
+                        distance[v] = candidate;
+                        parent[v] = Some((u, k));
+                        last = Some(v);
+                    }
+                }
+            }
+            if last.is_none() {
+                return Ok(distance);
+            }
+        }
+
+        // A node relaxed on the last pass leads back, through its parents, into the cycle.
+        let Some(mut v) = last else {
+            return Ok(distance);
+        };
+        for _ in 0..n {
+            v = parent[v].expect("a relaxed node has a parent").0;
+        }
+
+        let mut cycle = Vec::new();
+        let start = v;
+        loop {
+            let (u, k) = parent[v].expect("every node on the cycle has a parent");
+            cycle.push((u, k));
+            v = u;
+            if v == start {
+                break;
+            }
+        }
+        Err(cycle)
+    }
+
+    /// Sends as much flow as every arc on `path` allows through it.
+    fn augment(&mut self, path: &[(usize, usize)]) {
+        let residual = &mut self.network.residual;
+        if let Some(bottleneck) = path
+            .iter()
+            .map(|&(u, k)| residual.capacity[u][k])
+            .reduce(flow::min)
+        {
+            for &(u, k) in path {
+                residual.push(u, k, bottleneck);
+            }
+        }
+    }
+
+    /// Searches a cheapest path from the source to the sink with Dijkstra's algorithm,
+    /// on the costs reduced by `potential`, and raises the potentials by the distances found.
+    fn cheapest_path(&self, potential: &mut [C]) -> Option<Vec<(usize, usize)>> {
+        let residual = &self.network.residual;
+        let (s, t) = (self.network.source, self.network.sink);
+        let n = residual.arcs.0.len();
+        let mut distance: Vec<Option<C>> = vec![None; n];
+        let mut parent: Vec<Option<(usize, usize)>> = vec![None; n];
+        let mut settled = vec![false; n];
+        let mut heap = BinaryHeap::from([MinScored(C::zero(), s)]);
+        distance[s] = Some(C::zero());
+
+        while let Some(MinScored(d, u)) = heap.pop() {
+            if settled[u] {
+                continue;
+            }
+            settled[u] = true;
+
+            for (k, v) in residual.open_arcs(u) {
+                let candidate = d + self.cost[u][k] + potential[u] - potential[v];
+                if !settled[v] && distance[v].is_none_or(|old| candidate < old) {
+                    distance[v] = Some(candidate);
+                    parent[v] = Some((u, k));
+                    heap.push(MinScored(candidate, v));
+                }
+            }
+        }
+
+        distance[t]?;
+        for (p, d) in potential.iter_mut().zip(distance) {
+            if let Some(d) = d {
+                *p = *p + d;
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut v = t;
+        while let Some((u, k)) = parent[v] {
+            path.push((u, k));
+            v = u;
+        }
+        Some(path)
+    }
+
+    /// Reads the flow and its cost off the residual graph, or returns `None` if the flow
+    /// through some edge doesn't fit in the cost type.
+    fn into_min_cost_flow(self) -> Option<MinCostFlow<Node, W, C>> {
+        let cost = self
+            .network
+            .edge_flows()
+            .try_fold(C::zero(), |total, (u, k, flow)| {
+                Some(total + C::try_from(flow).ok()? * self.cost[u][k])
+            })?;
+
+        Some(MinCostFlow {
+            value: self.network.value(),
+            cost,
+            flows: self.network.flows(),
+            residual: self.network.residual,
+        })
+    }
+}
+
+/// Computes a minimum cost maximum flow with **successive shortest paths**: the flow is
+/// repeatedly augmented through a cheapest path on the residual graph.
+///
+/// The paths are found with Dijkstra's algorithm on costs reduced by node potentials,
+/// which keeps them non-negative even though reverse arcs have negative costs.
+/// The first potentials come from Bellman-Ford, so edge costs may be negative,
+/// but returns `None` if there's a cycle with negative total cost.
+pub(crate) fn successive_shortest_paths<Node, W, C, G>(
+    graph: &G,
+    source: Node,
+    sink: Node,
+    cost: &HashMap<(Node, Node), C>,
+) -> Option<MinCostFlow<Node, W, C>>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    C: Weight + Mul<Output = C> + Neg<Output = C> + TryFrom<W>,
+    G: WeightedGraph<Node, W>,
+{
+    let mut network = CostNetwork::new(graph, source, sink, cost)?;
+    let mut potential = network.potentials().ok()?;
+
+    while let Some(path) = network.cheapest_path(&mut potential) {
+        network.augment(&path);
+    }
+
+    network.into_min_cost_flow()
+}
+
+/// Computes a minimum cost maximum flow by **cycle cancelling**: a maximum flow is found
+/// with Edmonds-Karp, and then flow is pushed around cycles of negative total cost on the
+/// residual graph, found with Bellman-Ford, until there are none left.
+///
+/// It's slower than successive shortest paths, but also handles negative cost cycles.
+/// With integer capacities the flow stays integral and every cancelled cycle lowers
+/// its cost, so there are finitely many rounds; with fractional capacities there's
+/// no such bound, and it may not terminate.
+pub(crate) fn cycle_cancelling<Node, W, C, G>(
+    graph: &G,
+    source: Node,
+    sink: Node,
+    cost: &HashMap<(Node, Node), C>,
+) -> Option<MinCostFlow<Node, W, C>>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    C: Weight + Mul<Output = C> + Neg<Output = C> + TryFrom<W>,
+    G: WeightedGraph<Node, W>,
+{
+    let mut network = CostNetwork::new(graph, source, sink, cost)?;
+    let (s, t) = (network.network.source, network.network.sink);
+    flow::augment_along_shortest_paths(&mut network.network.residual, s, t);
+
+    while let Err(cycle) = network.potentials() {
+        network.augment(&cycle);
+    }
+
+    network.into_min_cost_flow()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::MinCostFlow;
+    use crate::graphs::{AdjacencyMatrix, WeightedAdjacencyList};
+    use crate::test_rng::XorShift;
+    use crate::{Graph, WeightedGraph};
+
+    /// Checks the capacity and conservation constraints of `flow` on `graph`.
+    fn is_flow(
+        graph: &WeightedAdjacencyList<i64>,
+        flow: &MinCostFlow<usize, i64, i64>,
+        source: usize,
+        sink: usize,
+    ) -> bool {
+        let mut balance: HashMap<usize, i64> = HashMap::new();
+        for (&(u, v), &f) in flow.flows() {
+            if f < 0 || Some(f) > graph.edge_weight(u, v) {
+                return false;
+            }
+            *balance.entry(u).or_default() -= f;
+            *balance.entry(v).or_default() += f;
+        }
+
+        graph
+            .nodes()
+            .filter(|&n| n != source && n != sink)
+            .all(|n| balance.get(&n).copied().unwrap_or(0) == 0)
+            && balance.get(&sink).copied().unwrap_or(0) == flow.value()
+    }
+
+    /// Builds a transportation model: the source 0 supplies the warehouses 1 (20 units)
+    /// and 2 (30 units), which ship to the stores 3, 4 and 5, which demand 10, 25 and 15
+    /// units from the sink 6. Returns the network and the cost of shipping per unit.
+    fn transportation() -> (WeightedAdjacencyList<i64>, HashMap<(usize, usize), i64>) {
+        let mut graph = WeightedAdjacencyList(vec![vec![]; 7]);
+        let mut costs = HashMap::new();
+        for (u, v, capacity, cost) in [
+            (0, 1, 20, 0),
+            (0, 2, 30, 0),
+            (1, 3, 100, 8),
+            (1, 4, 100, 6),
+            (1, 5, 100, 10),
+            (2, 3, 100, 9),
+            (2, 4, 100, 12),
+            (2, 5, 100, 13),
+            (3, 6, 10, 0),
+            (4, 6, 25, 0),
+            (5, 6, 15, 0),
+        ] {
+            graph.add_weighted_edge(u, v, capacity);
+            costs.insert((u, v), cost);
+        }
+        (graph, costs)
+    }
+
+    #[test]
+    fn cheapest_transportation_plan() {
+        let (graph, costs) = transportation();
+
+        for flow in [
+            graph.successive_shortest_paths(0, 6, &costs),
+            graph.cycle_cancelling(0, 6, &costs),
+        ] {
+            let flow = flow.expect("the network has no negative cycle");
+
+            // Warehouse 1 sends everything to store 4, where it saves the most.
+            assert_eq!(flow.value(), 50);
+            assert_eq!(flow.cost(), 20 * 6 + 10 * 9 + 5 * 12 + 15 * 13);
+            assert_eq!(flow.flow(1, 4), 20);
+            assert_eq!(flow.flow(2, 4), 5);
+            assert!(is_flow(&graph, &flow, 0, 6));
+        }
+    }
+
+    #[test]
+    fn negative_costs_and_cycles() {
+        // 0 -(2)-> 1 -(2)-> 3
+        // '-(2)-> 2 -(2)---^
+        // with 1 -> 2 -> 1 as a cycle of negative cost.
+        let mut graph = WeightedAdjacencyList(vec![vec![]; 4]);
+        let mut costs = HashMap::new();
+        for (u, v, cost) in [
+            (0, 1, 1),
+            (1, 3, 1),
+            (0, 2, -1),
+            (2, 3, 1),
+            (1, 2, -3),
+            (2, 1, 1),
+        ] {
+            graph.add_weighted_edge(u, v, 2);
+            costs.insert((u, v), cost);
+        }
+
+        assert!(graph.successive_shortest_paths(0, 3, &costs).is_none());
+
+        let flow = graph
+            .cycle_cancelling(0, 3, &costs)
+            .expect("cycle cancelling accepts negative cycles");
+
+        assert_eq!(flow.value(), 4);
+        // The paths cost 4, and running two units around the cycle saves 2 * (3 - 1).
+        assert_eq!(flow.flow(1, 2), 2);
+        assert_eq!(flow.cost(), 0);
+        assert!(is_flow(&graph, &flow, 0, 3));
+
+        graph.remove_edge(2, 1);
+        let Some(shortest) = graph.successive_shortest_paths(0, 3, &costs) else {
+            panic!("the negative cycle was removed");
+        };
+        let Some(cancelled) = graph.cycle_cancelling(0, 3, &costs) else {
+            panic!("the negative cycle was removed");
+        };
+
+        // Every edge into 3 is saturated, so none of the flow fits through 1 -> 2.
+        assert_eq!(shortest.cost(), cancelled.cost());
+        assert_eq!(shortest.value(), 4);
+        assert_eq!(shortest.flow(1, 2), 0);
+        assert_eq!(shortest.cost(), 4);
+    }
+
+    #[test]
+    fn both_variants_agree_with_max_flow() {
+        let mut rng = XorShift::new(0x1234_5678_9abc_def1);
+
+        for _ in 0..10 {
+            let mut graph = WeightedAdjacencyList(vec![vec![]; 7]);
+            let mut costs = HashMap::new();
+            for u in 0..7 {
+                for v in 0..7 {
+                    if u != v && rng.below(100) < 35 {
+                        graph.add_weighted_edge(u, v, (rng.below(9) + 1) as i64);
+                        costs.insert((u, v), rng.below(10) as i64);
+                    }
+                }
+            }
+
+            let shortest = graph
+                .successive_shortest_paths(0, 6, &costs)
+                .expect("costs are non-negative");
+            let cancelled = graph
+                .cycle_cancelling(0, 6, &costs)
+                .expect("costs are non-negative");
+            let max_flow = graph.dinic(0, 6).expect("0 and 6 are distinct nodes");
+
+            assert_eq!(shortest.value(), max_flow.value());
+            assert_eq!(cancelled.value(), max_flow.value());
+            assert_eq!(shortest.cost(), cancelled.cost());
+            assert!(is_flow(&graph, &shortest, 0, 6));
+            assert!(is_flow(&graph, &cancelled, 0, 6));
+        }
+    }
+
+    #[test]
+    fn costs_of_another_type() {
+        // 0 -> 1 -> 3
+        // '--> 2 --^
+        // on an adjacency matrix, whose capacities are unsigned.
+        let mut graph = AdjacencyMatrix(vec![vec![0; 4]; 4]);
+        for (u, v, capacity) in [(0, 1, 3), (1, 3, 2), (0, 2, 2), (2, 3, 3)] {
+            graph.add_weighted_edge(u, v, capacity);
+        }
+        let mut costs = HashMap::from([((0, 1), 1), ((1, 3), -2), ((0, 2), 4)]);
+
+        // The edge 2 -> 3 has no cost.
+        assert!(graph.successive_shortest_paths(0, 3, &costs).is_none());
+        assert!(graph.cycle_cancelling(0, 3, &costs).is_none());
+
+        costs.insert((2, 3), 0);
+        let flow = graph
+            .successive_shortest_paths(0, 3, &costs)
+            .expect("every edge has a cost");
+
+        assert_eq!(flow.value(), 4);
+        assert_eq!(flow.cost(), 2 * (1 - 2) + 2 * 4);
+
+        // Integer capacities with fractional costs.
+        let graph = WeightedAdjacencyList(vec![vec![(1, 2u32)], vec![]]);
+        let costs = HashMap::from([((0, 1), 0.25)]);
+
+        let flow = graph
+            .cycle_cancelling(0, 1, &costs)
+            .expect("the edge has a cost");
+
+        assert_eq!(flow.cost(), 0.5);
+    }
+
+    #[test]
+    fn invalid_terminals() {
+        let graph = WeightedAdjacencyList(vec![vec![(1, 3)], vec![]]);
+        let costs = HashMap::from([((0, 1), 1)]);
+
+        assert!(graph.successive_shortest_paths(0, 0, &costs).is_none());
+        assert!(graph.cycle_cancelling(0, 2, &costs).is_none());
+    }
+}
@@ -0,0 +1,410 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::Display;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{Error, Write};
+
+use crate::UndirectedGraph;
+use crate::graphs::AdjacencyList;
+use crate::shortest_paths::MinScored;
+use crate::union_find::UnionFind;
+use crate::weighted_graph::{Weight, WeightedGraph};
+
+/// Represents a **minimum spanning forest** of a weighted undirected graph:
+/// a minimum spanning tree for each of its connected components.
+///
+/// The forest is also kept as an undirected [`AdjacencyList`], where each node is
+/// identified by its position on [`Graph::nodes`](crate::Graph::nodes) of the original graph.
+#[derive(Debug, Clone)]
+pub struct SpanningForest<Node, W> {
+    edges: Vec<(Node, Node, W)>,
+    total: W,
+    forest: AdjacencyList,
+}
+
+impl<Node, W> SpanningForest<Node, W>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+{
+    /// Returns the edges of the forest, as `(u, v, weight)`, in the order they were chosen.
+    pub fn edges(&self) -> &[(Node, Node, W)] {
+        &self.edges
+    }
+
+    /// Returns the sum of the weights of every edge of the forest.
+    pub fn total_weight(&self) -> W {
+        self.total
+    }
+
+    /// Returns the forest as an undirected [`AdjacencyList`].
+    pub fn forest(&self) -> &AdjacencyList {
+        &self.forest
+    }
+}
+
+impl<Node, W> SpanningForest<Node, W>
+where
+    Node: Eq + Hash + Copy + Display,
+    W: Weight + Display,
+{
+    /// Exports `graph` to a DOT file for visualization, highlighting the edges of the forest.
+    ///
+    /// Every edge is labeled with its weight. Forest edges are drawn bold and red,
+    /// and the remaining edges of `graph` dashed and gray.
+    ///
+    /// # Arguments
+    /// * `graph` - The graph this forest was computed from.
+    /// * `path` - The path to the output DOT file. If it's inside a folder, this folder must exists earlier.
+    pub fn export_to_dot<G>(&self, graph: &G, mut path: String) -> Result<(), Error>
+    where
+        G: WeightedGraph<Node, W>,
+    {
+        if !path.contains(".dot") {
+            path += ".dot";
+        }
+
+        let mut file: File = File::create(&path)?;
+
+        writeln!(file, "graph G {{")?;
+        writeln!(file, "  rankdir=LR;")?;
+        writeln!(file, "  node [shape=circle];")?;
+
+        let tree_edges: HashSet<(Node, Node)> = self
+            .edges
+            .iter()
+            .flat_map(|&(u, v, _)| [(u, v), (v, u)])
+            .collect();
+        let index: HashMap<Node, usize> = graph.nodes().enumerate().map(|(i, n)| (n, i)).collect();
+
+        for node in graph.nodes() {
+            writeln!(file, " {} ", node)?;
+            for (neighbor, w) in graph.weighted_neighbors(node) {
+                if index[&node] >= index[&neighbor] {
+                    continue;
+                }
+                if tree_edges.contains(&(node, neighbor)) {
+                    writeln!(
+                        file,
+                        " {} -- {} [label=\"{}\", color=red, penwidth=2]; ",
+                        node, neighbor, w
+                    )?;
+                } else {
+                    writeln!(
+                        file,
+                        " {} -- {} [label=\"{}\", color=gray, style=dashed]; ",
+                        node, neighbor, w
+                    )?;
+                }
+            }
+        }
+
+        writeln!(file, " }}")?;
+
+        Ok(())
+    }
+}
+
+/// The nodes and edges of a weighted undirected graph, with nodes replaced by their
+/// position on [`Graph::nodes`](crate::Graph::nodes).
+///
+/// Each undirected edge is kept once, as `(i, j, weight)` with `i < j`, and the edges are
+/// sorted by weight. Self-loops are left out, as they never belong to a spanning forest.
+struct IndexedEdges<Node, W> {
+    nodes: Vec<Node>,
+    edges: Vec<(usize, usize, W)>,
+}
+
+impl<Node, W> IndexedEdges<Node, W>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+{
+    fn new<G: WeightedGraph<Node, W>>(graph: &G) -> Self {
+        let nodes: Vec<Node> = graph.nodes().collect();
+        let index: HashMap<Node, usize> = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+
+        let mut edges: Vec<(usize, usize, W)> = Vec::new();
+        for (i, &n) in nodes.iter().enumerate() {
+            for (m, w) in graph.weighted_neighbors(n) {
+                let j = index[&m];
+                if i < j {
+                    edges.push((i, j, w));
+                }
+            }
+        }
+        edges.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        Self { nodes, edges }
+    }
+
+    /// Builds the forest made by the edges at positions `chosen` of the sorted edge list.
+    fn forest(&self, chosen: impl IntoIterator<Item = usize>) -> SpanningForest<Node, W> {
+        let mut forest = AdjacencyList(vec![Vec::new(); self.nodes.len()]);
+        let mut edges = Vec::with_capacity(self.nodes.len().saturating_sub(1));
+        let mut total = W::zero();
+
+        for e in chosen {
+            let (i, j, w) = self.edges[e];
+            forest.add_undirected_edge(i, j);
+            edges.push((self.nodes[i], self.nodes[j], w));
+            total = total + w;
+        }
+
+        SpanningForest {
+            edges,
+            total,
+            forest,
+        }
+    }
+}
+
+/// Computes a minimum spanning forest with **Kruskal's algorithm**: the edges are taken
+/// by increasing weight, skipping those that would close a cycle.
+pub(crate) fn kruskal<Node, W, G>(graph: &G) -> SpanningForest<Node, W>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    G: WeightedGraph<Node, W> + UndirectedGraph<Node>,
+{
+    let indexed = IndexedEdges::new(graph);
+    let mut sets = UnionFind::new(indexed.nodes.len());
+
+    let chosen: Vec<usize> = (0..indexed.edges.len())
+        .filter(|&e| {
+            let (i, j, _) = indexed.edges[e];
+            sets.union(i, j)
+        })
+        .collect();
+
+    indexed.forest(chosen)
+}
+
+/// Computes a minimum spanning forest with **Prim's algorithm**: each tree grows from
+/// its first node on [`Graph::nodes`](crate::Graph::nodes), always through the lightest edge leaving it.
+pub(crate) fn prim<Node, W, G>(graph: &G) -> SpanningForest<Node, W>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    G: WeightedGraph<Node, W> + UndirectedGraph<Node>,
+{
+    let indexed = IndexedEdges::new(graph);
+    let n = indexed.nodes.len();
+
+    let mut incident: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (e, &(i, j, _)) in indexed.edges.iter().enumerate() {
+        incident[i].push(e);
+        incident[j].push(e);
+    }
+
+    let mut in_tree = vec![false; n];
+    let mut chosen = Vec::with_capacity(n.saturating_sub(1));
+    let mut heap: BinaryHeap<MinScored<W, (usize, usize)>> = BinaryHeap::new();
+
+    for root in 0..n {
+        if in_tree[root] {
+            continue;
+        }
+
+        in_tree[root] = true;
+        for &e in &incident[root] {
+            heap.push(MinScored(indexed.edges[e].2, (e, root)));
+        }
+
+        while let Some(MinScored(_, (e, from))) = heap.pop() {
+            let (i, j, _) = indexed.edges[e];
+            let to = if i == from { j } else { i };
+            if in_tree[to] {
+                continue;
+            }
+
+            in_tree[to] = true;
+            chosen.push(e);
+            for &next in &incident[to] {
+                heap.push(MinScored(indexed.edges[next].2, (next, to)));
+            }
+        }
+    }
+
+    indexed.forest(chosen)
+}
+
+/// Picks, for every component, the lightest edge that leaves it.
+///
+/// `component` maps each node to the id of its component. Since the edges are sorted by
+/// weight, the lightest edge is the first one on the list, which also breaks ties consistently.
+/// Each edge is looked at on its own, so this pass can be split among threads and the
+/// partial results merged by taking the smallest position.
+fn cheapest_leaving_edges<W>(
+    edges: &[(usize, usize, W)],
+    component: &[usize],
+) -> Vec<Option<usize>> {
+    let mut cheapest: Vec<Option<usize>> = vec![None; component.len()];
+
+    for (e, &(i, j, _)) in edges.iter().enumerate() {
+        let (ci, cj) = (component[i], component[j]);
+        if ci == cj {
+            continue;
+        }
+        for c in [ci, cj] {
+            if cheapest[c].is_none_or(|current| e < current) {
+                cheapest[c] = Some(e);
+            }
+        }
+    }
+    cheapest
+}
+
+/// Computes a minimum spanning forest with **Borůvka's algorithm**: on every round, each
+/// component is joined to another one through the lightest edge that leaves it.
+///
+/// The components at least halve on every round, so there are at most `log n` rounds.
+pub(crate) fn boruvka<Node, W, G>(graph: &G) -> SpanningForest<Node, W>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    G: WeightedGraph<Node, W> + UndirectedGraph<Node>,
+{
+    let indexed = IndexedEdges::new(graph);
+    let n = indexed.nodes.len();
+    let mut sets = UnionFind::new(n);
+    let mut chosen = Vec::with_capacity(n.saturating_sub(1));
+
+    loop {
+        let component: Vec<usize> = (0..n).map(|i| sets.find_mut(i)).collect();
+        let cheapest = cheapest_leaving_edges(&indexed.edges, &component);
+
+        let mut merged = false;
+        for e in cheapest.into_iter().flatten() {
+            let (i, j, _) = indexed.edges[e];
+            if sets.union(i, j) {
+                chosen.push(e);
+                merged = true;
+            }
+        }
+
+        if !merged {
+            break;
+        }
+    }
+
+    indexed.forest(chosen)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::graphs::{AdjacencyList, WeightedAdjacencyList};
+    use crate::{UndirectedGraph, WeightedGraph};
+
+    /// Checks that `forest` is a spanning forest of `graph`: acyclic and with the same components.
+    fn spans<G: UndirectedGraph<usize>>(graph: &G, forest: &AdjacencyList) -> bool {
+        let components = graph.connected_components();
+        forest.undirected_size() + components.count() == graph.order()
+            && forest.connected_components().count() == components.count()
+    }
+
+    /// Builds an undirected graph with 9 nodes and 14 edges, whose minimum spanning trees weigh 37.
+    fn example_graph() -> WeightedAdjacencyList<i32> {
+        let mut graph = WeightedAdjacencyList(vec![vec![]; 9]);
+        for (u, v, w) in [
+            (0, 1, 4),
+            (0, 7, 8),
+            (1, 2, 8),
+            (1, 7, 11),
+            (2, 3, 7),
+            (2, 8, 2),
+            (2, 5, 4),
+            (3, 4, 9),
+            (3, 5, 14),
+            (4, 5, 10),
+            (5, 6, 2),
+            (6, 7, 1),
+            (6, 8, 6),
+            (7, 8, 7),
+        ] {
+            graph.add_undirected_weighted_edge(u, v, w);
+        }
+        graph
+    }
+
+    #[test]
+    fn all_algorithms_find_minimum_spanning_tree() {
+        let graph = example_graph();
+
+        for mst in [graph.kruskal(), graph.prim(), graph.boruvka()] {
+            assert_eq!(mst.total_weight(), 37);
+            assert_eq!(mst.edges().len(), 8);
+            assert!(spans(&graph, mst.forest()));
+            for &(u, v, w) in mst.edges() {
+                assert_eq!(graph.edge_weight(u, v), Some(w));
+            }
+        }
+    }
+
+    #[test]
+    fn spanning_forest_of_disconnected_graph() {
+        // 0 -(1.5)- 1 -(0.5)- 2 -(2.0)- 0    3 -(1.0)- 4    5
+        let mut graph: WeightedAdjacencyList<f64> = WeightedAdjacencyList(vec![vec![]; 6]);
+        graph.add_undirected_weighted_edge(0, 1, 1.5);
+        graph.add_undirected_weighted_edge(1, 2, 0.5);
+        graph.add_undirected_weighted_edge(2, 0, 2.0);
+        graph.add_undirected_weighted_edge(3, 4, 1.0);
+        graph.add_undirected_weighted_edge(5, 5, 0.1);
+
+        for mst in [graph.kruskal(), graph.prim(), graph.boruvka()] {
+            assert_eq!(mst.total_weight(), 3.0);
+            assert_eq!(mst.edges().len(), 3);
+            assert_eq!(mst.forest().connected_components().count(), 3);
+            assert!(spans(&graph, mst.forest()));
+        }
+
+        let empty: WeightedAdjacencyList<i32> = WeightedAdjacencyList::default();
+        assert_eq!(empty.kruskal().total_weight(), 0);
+        assert!(empty.boruvka().edges().is_empty());
+    }
+
+    #[test]
+    fn nan_weights_are_picked_last() {
+        // A cycle 0 - 1 - 2 - 3 - 4 - 5 - 0 where two edges weigh NaN.
+        let mut graph: WeightedAdjacencyList<f64> = WeightedAdjacencyList(vec![vec![]; 6]);
+        for (u, v, w) in [
+            (0, 1, 3.0),
+            (1, 2, f64::NAN),
+            (2, 3, 1.0),
+            (3, 4, 5.0),
+            (4, 5, f64::NAN),
+            (5, 0, 2.0),
+        ] {
+            graph.add_undirected_weighted_edge(u, v, w);
+        }
+
+        for mst in [graph.kruskal(), graph.prim(), graph.boruvka()] {
+            let nan_edges = mst.edges().iter().filter(|(_, _, w)| w.is_nan()).count();
+
+            assert_eq!(mst.edges().len(), 5);
+            assert_eq!(nan_edges, 1);
+            assert!(spans(&graph, mst.forest()));
+        }
+    }
+
+    #[test]
+    fn spanning_tree_to_dot() {
+        let graph = example_graph();
+        let mst = graph.kruskal();
+        let name = format!("spanning_tree_{}.dot", std::process::id());
+        let path = std::env::temp_dir().join(name);
+
+        let result = mst.export_to_dot(&graph, path.to_string_lossy().into_owned());
+        let content = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+
+        assert!(result.is_ok());
+
+        let content = content.expect("the DOT file was written");
+        assert_eq!(content.matches("color=red").count(), 8);
+        assert_eq!(content.matches("color=gray").count(), 6);
+        assert!(content.contains(" 6 -- 7 [label=\"1\", color=red, penwidth=2]; "));
+    }
+}
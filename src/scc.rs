@@ -0,0 +1,310 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::Graph;
+use crate::graph::{DfsEvent, DfsForestIter, DfsIter, ForestEvent};
+use crate::graphs::AdjacencyList;
+
+/// Represents an iterator over the **strongly connected components** of a digraph,
+/// found by Tarjan's algorithm.
+///
+/// A DFS is started from every node not yet visited, in [`Graph::nodes`] order.
+/// The components are yielded in reverse topological order of the condensation:
+/// no component has an edge to a component yielded after it.
+pub struct TarjanSccIter<'a, Node, G>
+where
+    G: Graph<Node>,
+    Node: Eq + Hash + Copy,
+    Self: 'a,
+{
+    dfs: DfsForestIter<'a, Node, G>,
+    index: HashMap<Node, usize>,
+    lowlink: HashMap<Node, usize>,
+    parent: HashMap<Node, Node>,
+    stack: Vec<Node>,
+    on_stack: HashSet<Node>,
+}
+
+impl<'a, Node, G> TarjanSccIter<'a, Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    /// Creates a new Tarjan iterator over the whole graph.
+    pub(crate) fn new(graph: &'a G) -> Self {
+        Self {
+            dfs: graph.dfs_all(),
+            index: HashMap::with_capacity(graph.order()),
+            lowlink: HashMap::with_capacity(graph.order()),
+            parent: HashMap::with_capacity(graph.order()),
+            stack: Vec::new(),
+            on_stack: HashSet::new(),
+        }
+    }
+}
+
+impl<'a, Node, G> Iterator for TarjanSccIter<'a, Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    type Item = Vec<Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let ForestEvent::Event(event) = self.dfs.next()? else {
+                continue;
+            };
+
+            match event {
+                DfsEvent::Discover(node, parent) => {
+                    let index = self.index.len();
+                    self.index.insert(node, index);
+                    self.lowlink.insert(node, index);
+                    if let Some(parent) = parent {
+                        self.parent.insert(node, parent);
+                    }
+                    self.stack.push(node);
+                    self.on_stack.insert(node);
+                }
+                DfsEvent::NonTreeEdge(u, v) => {
+                    if self.on_stack.contains(&v) {
+                        let low = self.lowlink[&u].min(self.index[&v]);
+                        self.lowlink.insert(u, low);
+                    }
+                }
+                DfsEvent::Finish(node) => {
+                    let low = self.lowlink[&node];
+                    if let Some(&parent) = self.parent.get(&node) {
+                        let parent_low = self.lowlink[&parent].min(low);
+                        self.lowlink.insert(parent, parent_low);
+                    }
+
+                    if low == self.index[&node] {
+                        let mut component = Vec::new();
+                        while let Some(member) = self.stack.pop() {
+                            self.on_stack.remove(&member);
+                            component.push(member);
+                            if member == node {
+                                break;
+                            }
+                        }
+                        return Some(component);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Represents an iterator over the **strongly connected components** of a digraph,
+/// found by Kosaraju's algorithm.
+///
+/// The finishing order is computed upfront by a DFS over the reversed graph. Then each
+/// call to `next` runs a DFS over the graph from the unvisited node that finished last,
+/// and the nodes it discovers form a component. The components are yielded in reverse
+/// topological order of the condensation, just like [`TarjanSccIter`].
+pub struct KosarajuSccIter<'a, Node, G>
+where
+    G: Graph<Node>,
+    Node: Eq + Hash + Copy,
+    Self: 'a,
+{
+    dfs: DfsIter<'a, Node, G>,
+    finish_order: Vec<Node>,
+}
+
+impl<'a, Node, G> KosarajuSccIter<'a, Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    /// Creates a new Kosaraju iterator over the whole graph.
+    pub(crate) fn new(graph: &'a G) -> Self {
+        let reversed = graph.reversed();
+        let mut finish_order = Vec::with_capacity(graph.order());
+        for event in reversed.dfs_all() {
+            if let ForestEvent::Event(DfsEvent::Finish(node)) = event {
+                finish_order.push(node);
+            }
+        }
+
+        Self {
+            dfs: DfsIter::unstarted(graph),
+            finish_order,
+        }
+    }
+}
+
+impl<'a, Node, G> Iterator for KosarajuSccIter<'a, Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    type Item = Vec<Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.dfs.new_start(self.finish_order.pop()?);
+
+            let component: Vec<Node> = self
+                .dfs
+                .by_ref()
+                .filter_map(|event| match event {
+                    DfsEvent::Discover(node, _) => Some(node),
+                    _ => None,
+                })
+                .collect();
+
+            if !component.is_empty() {
+                return Some(component);
+            }
+        }
+    }
+}
+
+/// Builds the condensation of `graph`, where each strongly connected component
+/// is collapsed into a single node.
+///
+/// The components are numbered in topological order, so every edge of the
+/// resulting DAG goes from a smaller to a larger component.
+pub(crate) fn condensation<Node, G>(graph: &G) -> (AdjacencyList, HashMap<Node, usize>)
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    let components: Vec<Vec<Node>> = TarjanSccIter::new(graph).collect();
+    let count = components.len();
+
+    let mut component_of: HashMap<Node, usize> = HashMap::with_capacity(graph.order());
+    for (i, component) in components.into_iter().enumerate() {
+        for node in component {
+            component_of.insert(node, count - 1 - i);
+        }
+    }
+
+    let mut dag = AdjacencyList(vec![Vec::new(); count]);
+    for u in graph.nodes() {
+        for v in graph.neighbors(u) {
+            let (cu, cv) = (component_of[&u], component_of[&v]);
+            if cu != cv {
+                dag.add_edge(cu, cv);
+            }
+        }
+    }
+
+    (dag, component_of)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::Error;
+
+    use crate::graphs::{AdjacencyList, AdjacencyMatrix, LabeledGraph};
+    use crate::{Graph, GraphIO};
+
+    static PATH: &str = "examples/data/";
+
+    /// Sorts each component and then the list of components, so results can be compared.
+    fn normalized<Node: Ord>(mut components: Vec<Vec<Node>>) -> Vec<Vec<Node>> {
+        for component in components.iter_mut() {
+            component.sort();
+        }
+        components.sort();
+        components
+    }
+
+    /// Checks that no component has an edge to a component that comes after it.
+    fn is_reverse_topological<G: Graph<usize>>(graph: &G, components: &[Vec<usize>]) -> bool {
+        let position: HashMap<usize, usize> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(i, component)| component.iter().map(move |&node| (node, i)))
+            .collect();
+
+        graph
+            .nodes()
+            .all(|u| graph.neighbors(u).all(|v| position[&v] <= position[&u]))
+    }
+
+    #[test]
+    fn scc_of_digraph_with_cycles() {
+        let result: Result<AdjacencyList, Error> =
+            GraphIO::import_from_file(PATH.to_owned() + "DIGRAFO1.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(list) = result {
+            let tarjan: Vec<Vec<usize>> = list.tarjan_scc().collect();
+            let kosaraju: Vec<Vec<usize>> = list.kosaraju_scc().collect();
+
+            assert!(is_reverse_topological(&list, &tarjan));
+            assert!(is_reverse_topological(&list, &kosaraju));
+            assert_eq!(normalized(tarjan.clone()), normalized(kosaraju));
+            assert_eq!(
+                normalized(tarjan),
+                vec![
+                    vec![0, 1, 2],
+                    vec![3, 4, 5, 6, 7, 8],
+                    vec![9],
+                    vec![10],
+                    vec![11, 12],
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn scc_on_adjacency_matrix() {
+        // 0 -> 1 <-> 2 -> 3 -> 3
+        let matrix = AdjacencyMatrix(vec![
+            vec![0, 1, 0, 0],
+            vec![0, 0, 1, 0],
+            vec![0, 1, 0, 1],
+            vec![0, 0, 0, 1],
+        ]);
+
+        let tarjan: Vec<Vec<usize>> = matrix
+            .tarjan_scc()
+            .map(|mut c| {
+                c.sort();
+                c
+            })
+            .collect();
+
+        assert_eq!(tarjan, vec![vec![3], vec![1, 2], vec![0]]);
+    }
+
+    #[test]
+    fn condensation_of_labeled_digraph() {
+        // Same digraph as DIGRAFO3.txt
+        let mut graph: LabeledGraph<char, AdjacencyList> = LabeledGraph::new();
+        for label in 'a'..='q' {
+            graph.add_labeled_node(label);
+        }
+        for edge in [
+            "ab", "ae", "af", "bc", "ca", "dc", "fe", "fg", "gd", "hi", "hl", "ij", "jg", "jk",
+            "ki", "lk", "lm", "mh", "no", "np", "nq", "om", "pq", "qn",
+        ] {
+            let mut labels = edge.chars();
+            if let (Some(n), Some(m)) = (labels.next(), labels.next()) {
+                graph.add_labeled_edge(&n, &m);
+            }
+        }
+
+        let (dag, component_of) = graph.condensation();
+
+        // {a, b, c, d, f, g}, {e}, {h, l, m}, {i, j, k}, {n, p, q}, {o}
+        assert_eq!(dag.order(), 6);
+        assert_eq!(dag.size(), 5);
+        assert_eq!(component_of[&'a'], component_of[&'g']);
+        assert_eq!(component_of[&'h'], component_of[&'m']);
+        assert_ne!(component_of[&'e'], component_of[&'f']);
+        assert!(dag.has_edge(component_of[&'n'], component_of[&'o']));
+        assert!(dag.has_edge(component_of[&'j'], component_of[&'d']));
+        for u in dag.nodes() {
+            assert!(dag.neighbors(u).all(|v| u < v));
+        }
+    }
+}
@@ -0,0 +1,624 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::weighted_graph::{Weight, WeightedGraph};
+
+/// Represents the result of a shortest path search from one or more sources.
+///
+/// It holds the distance of every reached node and its predecessor on a shortest
+/// path, so the path itself can be rebuilt with [`ShortestPaths::path_to`].
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<Node, W> {
+    distances: HashMap<Node, W>,
+    predecessors: HashMap<Node, Node>,
+}
+
+impl<Node, W> ShortestPaths<Node, W>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+{
+    /// Creates the result of a search from its distance and predecessor maps.
+    pub(crate) fn new(distances: HashMap<Node, W>, predecessors: HashMap<Node, Node>) -> Self {
+        Self {
+            distances,
+            predecessors,
+        }
+    }
+
+    /// Returns the distance from the closest source to `target`,
+    /// or `None` if `target` wasn't reached.
+    pub fn distance(&self, target: Node) -> Option<W> {
+        self.distances.get(&target).copied()
+    }
+
+    /// Returns the distances of every reached node.
+    pub fn distances(&self) -> &HashMap<Node, W> {
+        &self.distances
+    }
+
+    /// Returns the node that precedes `target` on its shortest path,
+    /// or `None` if `target` is a source or wasn't reached.
+    pub fn predecessor(&self, target: Node) -> Option<Node> {
+        self.predecessors.get(&target).copied()
+    }
+
+    /// Rebuilds the shortest path from its source to `target`, with both ends included.
+    ///
+    /// Returns `None` if `target` wasn't reached.
+    pub fn path_to(&self, target: Node) -> Option<Vec<Node>> {
+        if !self.distances.contains_key(&target) {
+            return None;
+        }
+
+        let mut path = vec![target];
+        let mut current = target;
+        while let Some(&previous) = self.predecessors.get(&current) {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// A cycle whose total weight is negative, found by a shortest path search.
+///
+/// The nodes are listed in the order the edges of the cycle are followed,
+/// and the last node has an edge back to the first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycle<Node>(pub Vec<Node>);
+
+/// Represents the result of an A* search from a start node to a goal node.
+///
+/// Besides the path found, it records how many nodes were expanded,
+/// which measures how well the heuristic guided the search.
+#[derive(Debug, Clone)]
+pub struct AStarSearch<Node, W> {
+    path: Option<(Vec<Node>, W)>,
+    expanded: usize,
+}
+
+impl<Node, W: Weight> AStarSearch<Node, W> {
+    /// Returns the path found from the start to the goal, with both ends included,
+    /// or `None` if the goal can't be reached.
+    pub fn path(&self) -> Option<&[Node]> {
+        self.path.as_ref().map(|(path, _)| path.as_slice())
+    }
+
+    /// Returns the total weight of the path found, or `None` if the goal can't be reached.
+    pub fn cost(&self) -> Option<W> {
+        self.path.as_ref().map(|&(_, cost)| cost)
+    }
+
+    /// Returns how many nodes had their neighbors explored during the search.
+    ///
+    /// A node is counted again if it's expanded again after a shorter path to it is found.
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+}
+
+/// An entry of the binary heap used by the shortest path searches.
+///
+/// The ordering is reversed, so that [`BinaryHeap`] behaves as a min-heap on the score.
+/// Scores are compared with [`Weight::total_cmp`], so a `NaN` can't break the heap.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MinScored<W, Node>(pub W, pub Node);
+
+impl<W: Weight, Node> PartialEq for MinScored<W, Node> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: Weight, Node> Eq for MinScored<W, Node> {}
+
+impl<W: Weight, Node> PartialOrd for MinScored<W, Node> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Weight, Node> Ord for MinScored<W, Node> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.total_cmp(&self.0)
+    }
+}
+
+/// Runs Dijkstra's algorithm from every node in `sources` at once.
+///
+/// If `target` is given, the search stops as soon as it's settled,
+/// and only the settled nodes are kept on the result.
+pub(crate) fn dijkstra<Node, W, G>(
+    graph: &G,
+    sources: impl IntoIterator<Item = Node>,
+    target: Option<Node>,
+) -> ShortestPaths<Node, W>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    G: WeightedGraph<Node, W>,
+{
+    let mut distances: HashMap<Node, W> = HashMap::with_capacity(graph.order());
+    let mut predecessors: HashMap<Node, Node> = HashMap::with_capacity(graph.order());
+    let mut settled: HashSet<Node> = HashSet::with_capacity(graph.order());
+    let mut heap = BinaryHeap::new();
+
+    for source in sources {
+        distances.insert(source, W::zero());
+        heap.push(MinScored(W::zero(), source));
+    }
+
+    while let Some(MinScored(distance, node)) = heap.pop() {
+        if !settled.insert(node) {
+            continue;
+        }
+        if Some(node) == target {
+            // The nodes left on the heap only hold tentative distances.
+            distances.retain(|n, _| settled.contains(n));
+            predecessors.retain(|n, _| settled.contains(n));
+            break;
+        }
+
+        for (neighbor, weight) in graph.weighted_neighbors(node) {
+            let candidate = distance + weight;
+            if distances
+                .get(&neighbor)
+                .is_none_or(|&current| candidate < current)
+            {
+                distances.insert(neighbor, candidate);
+                predecessors.insert(neighbor, node);
+                heap.push(MinScored(candidate, neighbor));
+            }
+        }
+    }
+
+    ShortestPaths::new(distances, predecessors)
+}
+
+/// Runs the A* search from `start` to `goal`.
+///
+/// `successors` lists the neighbors of a node together with the weight of the edge reaching
+/// them, and `heuristic` estimates the distance from a node to `goal`. The path found is a
+/// shortest one as long as the heuristic never overestimates that distance.
+pub(crate) fn astar<Node, W, I>(
+    start: Node,
+    goal: Node,
+    successors: impl Fn(Node) -> I,
+    heuristic: impl Fn(Node) -> W,
+) -> AStarSearch<Node, W>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    I: Iterator<Item = (Node, W)>,
+{
+    let mut distances: HashMap<Node, W> = HashMap::new();
+    let mut predecessors: HashMap<Node, Node> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    let mut expanded = 0;
+
+    distances.insert(start, W::zero());
+    heap.push(MinScored(heuristic(start), (start, W::zero())));
+
+    while let Some(MinScored(_, (node, distance))) = heap.pop() {
+        // Entries pushed before a shorter path to `node` was found are stale.
+        if distances.get(&node).is_some_and(|&best| best < distance) {
+            continue;
+        }
+        if node == goal {
+            let path = ShortestPaths::new(distances, predecessors).path_to(goal);
+            return AStarSearch {
+                path: path.map(|path| (path, distance)),
+                expanded,
+            };
+        }
+        expanded += 1;
+
+        for (neighbor, weight) in successors(node) {
+            let candidate = distance + weight;
+            if distances
+                .get(&neighbor)
+                .is_none_or(|&current| candidate < current)
+            {
+                distances.insert(neighbor, candidate);
+                predecessors.insert(neighbor, node);
+                heap.push(MinScored(
+                    candidate + heuristic(neighbor),
+                    (neighbor, candidate),
+                ));
+            }
+        }
+    }
+
+    AStarSearch {
+        path: None,
+        expanded,
+    }
+}
+
+/// Runs the Bellman-Ford algorithm from `source`.
+///
+/// Every edge is relaxed at most `order - 1` times. If an edge can still be relaxed
+/// after that, a negative cycle is reachable and it's returned.
+pub(crate) fn bellman_ford<Node, W, G>(
+    graph: &G,
+    source: Node,
+) -> Result<ShortestPaths<Node, W>, NegativeCycle<Node>>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    G: WeightedGraph<Node, W>,
+{
+    let edges: Vec<(Node, Node, W)> = graph
+        .nodes()
+        .flat_map(|u| graph.weighted_neighbors(u).map(move |(v, w)| (u, v, w)))
+        .collect();
+
+    let mut distances: HashMap<Node, W> = HashMap::with_capacity(graph.order());
+    let mut predecessors: HashMap<Node, Node> = HashMap::with_capacity(graph.order());
+    distances.insert(source, W::zero());
+
+    let relax = |distances: &mut HashMap<Node, W>, predecessors: &mut HashMap<Node, Node>| {
+        let mut relaxed = None;
+        for &(u, v, w) in &edges {
+            if let Some(&du) = distances.get(&u)
+                && distances.get(&v).is_none_or(|&dv| du + w < dv)
+            {
+                distances.insert(v, du + w);
+                predecessors.insert(v, u);
+                relaxed = Some(v);
+            }
+        }
+        relaxed
+    };
+
+    for _ in 1..graph.order() {
+        if relax(&mut distances, &mut predecessors).is_none() {
+            return Ok(ShortestPaths::new(distances, predecessors));
+        }
+    }
+
+    match relax(&mut distances, &mut predecessors) {
+        Some(node) => Err(predecessor_cycle(&predecessors, node)
+            .expect("a relaxation after |V| - 1 rounds leaves a cycle of predecessors")),
+        None => Ok(ShortestPaths::new(distances, predecessors)),
+    }
+}
+
+/// Runs the Shortest Path Faster Algorithm (SPFA), the queue-based variant of Bellman-Ford.
+///
+/// Only the neighbors of nodes whose distance changed are relaxed again. A node reached
+/// through a path with `order` or more edges means a negative cycle is reachable, which is
+/// then searched on the predecessors.
+pub(crate) fn spfa<Node, W, G>(
+    graph: &G,
+    source: Node,
+) -> Result<ShortestPaths<Node, W>, NegativeCycle<Node>>
+where
+    Node: Eq + Hash + Copy,
+    W: Weight,
+    G: WeightedGraph<Node, W>,
+{
+    let n = graph.order().max(1);
+    let mut distances: HashMap<Node, W> = HashMap::with_capacity(graph.order());
+    let mut predecessors: HashMap<Node, Node> = HashMap::with_capacity(graph.order());
+    let mut path_edges: HashMap<Node, usize> = HashMap::with_capacity(graph.order());
+    let mut in_queue: HashSet<Node> = HashSet::with_capacity(graph.order());
+    let mut queue = VecDeque::from([source]);
+
+    distances.insert(source, W::zero());
+    path_edges.insert(source, 0);
+    in_queue.insert(source);
+
+    while let Some(u) = queue.pop_front() {
+        in_queue.remove(&u);
+        let du = distances[&u];
+
+        for (v, w) in graph.weighted_neighbors(u) {
+            if distances.get(&v).is_none_or(|&dv| du + w < dv) {
+                distances.insert(v, du + w);
+                predecessors.insert(v, u);
+
+                let edges = path_edges[&u] + 1;
+                path_edges.insert(v, edges);
+                if edges.is_multiple_of(n)
+                    && let Some(cycle) = predecessor_cycle(&predecessors, v)
+                {
+                    return Err(cycle);
+                }
+
+                if in_queue.insert(v) {
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+
+    Ok(ShortestPaths::new(distances, predecessors))
+}
+
+/// Searches a cycle on the graph formed by the `predecessors` map.
+///
+/// The walk starts from `start`, then from every other node, so any cycle is found.
+/// Each node is walked over once, which keeps the search linear.
+fn predecessor_cycle<Node>(
+    predecessors: &HashMap<Node, Node>,
+    start: Node,
+) -> Option<NegativeCycle<Node>>
+where
+    Node: Eq + Hash + Copy,
+{
+    let mut walk_of: HashMap<Node, usize> = HashMap::with_capacity(predecessors.len());
+    let starts = std::iter::once(start).chain(predecessors.keys().copied());
+
+    for (walk, node) in starts.enumerate() {
+        let mut current = node;
+        loop {
+            match walk_of.get(&current) {
+                Some(&w) if w == walk => {
+                    let mut cycle = vec![current];
+                    let mut previous = predecessors[&current];
+                    while previous != current {
+                        cycle.push(previous);
+                        previous = predecessors[&previous];
+                    }
+                    cycle.reverse();
+                    return Some(NegativeCycle(cycle));
+                }
+                Some(_) => break,
+                None => {
+                    walk_of.insert(current, walk);
+                    match predecessors.get(&current) {
+                        Some(&previous) => current = previous,
+                        None => break,
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+
+    use super::NegativeCycle;
+    use crate::graphs::{AdjacencyList, AdjacencyMatrix, WeightedAdjacencyList};
+    use crate::{Graph, UndirectedGraph, WeightedGraph, WeightedGraphIO};
+
+    static PATH: &str = "examples/data/";
+
+    #[test]
+    fn dijkstra_on_weighted_adjacency_list() {
+        let result: Result<WeightedAdjacencyList<u32>, Error> =
+            WeightedGraphIO::import_weighted_from_file(PATH.to_owned() + "DIGRAFO_PONDERADO.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(list) = result {
+            let paths = list.dijkstra(0);
+
+            assert_eq!(paths.distance(0), Some(0));
+            assert_eq!(paths.distance(3), Some(20));
+            assert_eq!(paths.distance(5), Some(11));
+            assert_eq!(paths.path_to(4), Some(vec![0, 2, 3, 4]));
+            assert_eq!(paths.path_to(5), Some(vec![0, 2, 5]));
+            assert_eq!(paths.path_to(0), Some(vec![0]));
+        }
+    }
+
+    #[test]
+    fn dijkstra_on_adjacency_matrix() {
+        let result: Result<AdjacencyMatrix, Error> =
+            WeightedGraphIO::import_weighted_from_file(PATH.to_owned() + "DIGRAFO_PONDERADO.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(matrix) = result {
+            let paths = matrix.dijkstra(1);
+
+            assert_eq!(paths.distance(4), Some(21));
+            assert_eq!(paths.predecessor(4), Some(3));
+            assert_eq!(paths.distance(0), None);
+            assert_eq!(paths.path_to(0), None);
+        }
+    }
+
+    #[test]
+    fn dijkstra_single_pair_stops_early() {
+        // Graph: 0 -(1)-> 1 -(1)-> 2 -(1)-> 3
+        let list = WeightedAdjacencyList(vec![vec![(1, 1)], vec![(2, 1)], vec![(3, 1)], vec![]]);
+
+        let paths = list.dijkstra_to(0, 1);
+
+        assert_eq!(paths.path_to(1), Some(vec![0, 1]));
+        assert_eq!(paths.distance(2), None);
+        assert_eq!(paths.path_to(3), None);
+    }
+
+    #[test]
+    fn dijkstra_single_pair_drops_tentative_distances() {
+        // Graph: 0 -(1)-> 1 -(1)-> 2
+        //        '-----(10)-------^
+        let list = WeightedAdjacencyList(vec![vec![(1, 1), (2, 10)], vec![(2, 1)], vec![]]);
+
+        let paths = list.dijkstra_to(0, 1);
+
+        assert_eq!(paths.distance(1), Some(1));
+        assert_eq!(paths.distance(2), None);
+        assert_eq!(paths.path_to(2), None);
+        assert_eq!(list.dijkstra_to(0, 2).distance(2), Some(2));
+    }
+
+    #[test]
+    fn dijkstra_multi_source() {
+        // Graph: 0 -(5)-> 1 -(1)-> 2 <-(1)- 3
+        let list =
+            WeightedAdjacencyList(vec![vec![(1, 5.0)], vec![(2, 1.0)], vec![], vec![(2, 1.0)]]);
+
+        let paths = list.dijkstra_multi_source([0, 3]);
+
+        assert_eq!(paths.distance(2), Some(1.0));
+        assert_eq!(paths.path_to(2), Some(vec![3, 2]));
+        assert_eq!(paths.path_to(1), Some(vec![0, 1]));
+    }
+
+    /// Returns the total weight of a cycle, checking that each of its edges exists.
+    fn cycle_weight(list: &WeightedAdjacencyList<i64>, cycle: &[usize]) -> i64 {
+        cycle
+            .iter()
+            .zip(cycle.iter().cycle().skip(1))
+            .map(|(&u, &v)| list.edge_weight(u, v).expect("edge should exist"))
+            .sum()
+    }
+
+    #[test]
+    fn bellman_ford_with_negative_edges() {
+        // Graph: 0 -(4)-> 1 -(2)-> 3
+        //         \      ^
+        //         (5)   (-3)
+        //           \  /
+        //            2
+        let list = WeightedAdjacencyList(vec![
+            vec![(1, 4), (2, 5)],
+            vec![(3, 2)],
+            vec![(1, -3)],
+            vec![],
+        ]);
+
+        for result in [list.bellman_ford(0), list.spfa(0)] {
+            assert!(result.is_ok());
+
+            if let Ok(paths) = result {
+                assert_eq!(paths.distance(1), Some(2));
+                assert_eq!(paths.distance(3), Some(4));
+                assert_eq!(paths.path_to(3), Some(vec![0, 2, 1, 3]));
+            }
+        }
+    }
+
+    #[test]
+    fn bellman_ford_finds_negative_cycle() {
+        // Graph: 0 -(1)-> 1 -(-1)-> 2 -(-1)-> 3 -(2)-> 4
+        //                 ^                  /
+        //                  \------(1)-------
+        let list = WeightedAdjacencyList(vec![
+            vec![(1, 1)],
+            vec![(2, -1)],
+            vec![(3, -1)],
+            vec![(1, 1), (4, 2)],
+            vec![],
+        ]);
+
+        for result in [list.bellman_ford(0), list.spfa(0)] {
+            assert!(result.is_err());
+
+            if let Err(NegativeCycle(cycle)) = result {
+                let mut nodes = cycle.clone();
+                nodes.sort();
+                assert_eq!(nodes, vec![1, 2, 3]);
+                assert_eq!(cycle_weight(&list, &cycle), -1);
+            }
+        }
+    }
+
+    #[test]
+    fn negative_self_loop_is_a_cycle() {
+        // Graph: 0 -(3)-> 1, with a loop of weight -1 on 1
+        let list = WeightedAdjacencyList(vec![vec![(1, 3)], vec![(1, -1)]]);
+
+        assert_eq!(list.bellman_ford(0).err(), Some(NegativeCycle(vec![1])));
+        assert_eq!(list.spfa(0).err(), Some(NegativeCycle(vec![1])));
+    }
+
+    #[test]
+    fn unreachable_negative_cycle_is_ignored() {
+        // Graph: 0 -(2)-> 1    2 <-(-5)-> 3
+        let list = WeightedAdjacencyList(vec![vec![(1, 2)], vec![], vec![(3, -5)], vec![(2, -5)]]);
+
+        for result in [list.bellman_ford(0), list.spfa(0)] {
+            assert!(result.is_ok());
+
+            if let Ok(paths) = result {
+                assert_eq!(paths.distance(1), Some(2));
+                assert_eq!(paths.distance(2), None);
+            }
+        }
+    }
+
+    /// Builds a `width` x `height` grid where node `y * width + x` is linked
+    /// to its orthogonal neighbors, except for the `walls`.
+    fn grid(width: usize, height: usize, walls: &[usize]) -> AdjacencyList {
+        let mut list = AdjacencyList(vec![vec![]; width * height]);
+        for node in 0..width * height {
+            if walls.contains(&node) {
+                continue;
+            }
+            let right = node + 1;
+            let down = node + width;
+            if node % width + 1 < width && !walls.contains(&right) {
+                list.add_undirected_edge(node, right);
+            }
+            if down < width * height && !walls.contains(&down) {
+                list.add_undirected_edge(node, down);
+            }
+        }
+        list
+    }
+
+    #[test]
+    fn unweighted_astar_on_grid() {
+        // Grid: S . . . .
+        //       # # # # .
+        //       G . . . .
+        let list = grid(5, 3, &[5, 6, 7, 8]);
+        let manhattan = |node: usize| node % 5 + (node / 5).abs_diff(2);
+
+        let search = list.unweighted_astar(0, 10, manhattan);
+
+        assert_eq!(search.cost(), Some(10));
+        assert_eq!(
+            search.path(),
+            Some([0, 1, 2, 3, 4, 9, 14, 13, 12, 11, 10].as_slice())
+        );
+        assert!(search.expanded() <= list.order());
+    }
+
+    #[test]
+    fn unweighted_astar_heuristic_reduces_expansions() {
+        // Open 10 x 10 grid, from a corner to the opposite one.
+        let list = grid(10, 10, &[]);
+        let manhattan = |node: usize| (9 - node % 10) + (9 - node / 10);
+
+        let guided = list.unweighted_astar(0, 99, manhattan);
+        let blind = list.unweighted_astar(0, 99, |_| 0);
+
+        assert_eq!(guided.cost(), Some(18));
+        assert_eq!(blind.cost(), Some(18));
+        assert!(guided.expanded() < blind.expanded());
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let result: Result<WeightedAdjacencyList<u32>, Error> =
+            WeightedGraphIO::import_weighted_from_file(PATH.to_owned() + "DIGRAFO_PONDERADO.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(list) = result {
+            let search = list.astar(0, 4, |_| 0);
+
+            assert_eq!(search.cost(), list.dijkstra(0).distance(4));
+            assert_eq!(search.path(), Some([0, 2, 3, 4].as_slice()));
+
+            let unreachable = list.astar(4, 0, |_| 0);
+
+            assert_eq!(unreachable.path(), None);
+            assert_eq!(unreachable.cost(), None);
+            assert_eq!(unreachable.expanded(), 2);
+        }
+    }
+}
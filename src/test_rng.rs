@@ -0,0 +1,23 @@
+/// A xorshift generator, so tests can build the same pseudo-random inputs
+/// on every run without an external crate.
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    /// Creates a generator from a non-zero `seed`.
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Returns the next number of the sequence.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns the next number of the sequence, reduced below `bound`.
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
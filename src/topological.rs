@@ -0,0 +1,309 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::Graph;
+use crate::graph::{DfsEvent, Edge, ForestEvent};
+
+/// A directed cycle that keeps a graph from having a topological order.
+///
+/// The nodes are listed in the order the edges of the cycle are followed,
+/// and the last node has an edge back to the first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<Node>(pub Vec<Node>);
+
+/// Sorts the nodes of `graph` with Kahn's algorithm, repeatedly removing a node
+/// without incoming edges.
+///
+/// Ties are broken by the [`Graph::nodes`] order.
+pub(crate) fn kahn<Node, G>(graph: &G) -> Result<Vec<Node>, Cycle<Node>>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    let mut in_degree: HashMap<Node, usize> = graph.nodes().map(|n| (n, 0)).collect();
+    for n in graph.nodes() {
+        for m in graph.neighbors(n) {
+            *in_degree.entry(m).or_insert(0) += 1;
+        }
+    }
+
+    let mut queue: VecDeque<Node> = graph.nodes().filter(|n| in_degree[n] == 0).collect();
+    let mut order = Vec::with_capacity(graph.order());
+
+    while let Some(n) = queue.pop_front() {
+        order.push(n);
+        for m in graph.neighbors(n) {
+            let degree = in_degree.entry(m).or_insert(0);
+            *degree -= 1;
+            if *degree == 0 {
+                queue.push_back(m);
+            }
+        }
+    }
+
+    if order.len() < graph.order() {
+        return Err(find_cycle(graph).expect("nodes left by Kahn's algorithm lie on a cycle"));
+    }
+    Ok(order)
+}
+
+/// Sorts the nodes of `graph` by decreasing finishing time of a DFS
+/// over the whole graph.
+pub(crate) fn dfs<Node, G>(graph: &G) -> Result<Vec<Node>, Cycle<Node>>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    let mut order = Vec::with_capacity(graph.order());
+    let mut active: HashSet<Node> = HashSet::new();
+
+    for event in graph.dfs_all() {
+        match event {
+            ForestEvent::Event(DfsEvent::Discover(n, _)) => {
+                active.insert(n);
+            }
+            ForestEvent::Event(DfsEvent::Finish(n)) => {
+                active.remove(&n);
+                order.push(n);
+            }
+            ForestEvent::Event(DfsEvent::NonTreeEdge(_, m)) => {
+                if active.contains(&m) {
+                    return Err(
+                        find_cycle(graph).expect("an edge to an active node closes a cycle")
+                    );
+                }
+            }
+            ForestEvent::Root(_) => {}
+        }
+    }
+
+    order.reverse();
+    Ok(order)
+}
+
+/// Searches a directed cycle on `graph`, through the first back edge
+/// found by [`Graph::classify_all_edges`].
+pub(crate) fn find_cycle<Node, G>(graph: &G) -> Option<Cycle<Node>>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    let mut parent: HashMap<Node, Node> = HashMap::with_capacity(graph.order());
+
+    for event in graph.classify_all_edges() {
+        match event {
+            ForestEvent::Event(Edge::Tree(u, v)) => {
+                parent.insert(v, u);
+            }
+            ForestEvent::Event(Edge::Back(u, v) | Edge::ParentBack(u, v)) => {
+                let mut cycle = vec![u];
+                let mut current = u;
+                while current != v {
+                    current = parent[&current];
+                    cycle.push(current);
+                }
+                cycle.reverse();
+                return Some(Cycle(cycle));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Represents a lazy iterator over every topological order of a digraph.
+///
+/// The orders are generated by backtracking: each step picks, in [`Graph::nodes`] order,
+/// one of the remaining nodes without incoming edges from other remaining nodes.
+/// A graph with `n` nodes may have up to `n!` orders, so this is meant for small graphs.
+/// Nothing is yielded if the graph has a cycle.
+pub struct AllTopologicalOrders<Node> {
+    nodes: Vec<Node>,
+    successors: Vec<Vec<usize>>,
+    in_degree: Vec<usize>,
+    used: Vec<bool>,
+    order: Vec<usize>,
+    next_choice: Vec<usize>,
+    done: bool,
+}
+
+impl<Node> AllTopologicalOrders<Node>
+where
+    Node: Eq + Hash + Copy,
+{
+    /// Creates a new iterator over the topological orders of `graph`.
+    pub(crate) fn new<G: Graph<Node>>(graph: &G) -> Self {
+        let nodes: Vec<Node> = graph.nodes().collect();
+        let index: HashMap<Node, usize> = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+
+        let successors: Vec<Vec<usize>> = nodes
+            .iter()
+            .map(|&n| graph.neighbors(n).map(|m| index[&m]).collect())
+            .collect();
+        let mut in_degree = vec![0; nodes.len()];
+        for &m in successors.iter().flatten() {
+            in_degree[m] += 1;
+        }
+
+        Self {
+            used: vec![false; nodes.len()],
+            order: Vec::with_capacity(nodes.len()),
+            next_choice: vec![0; nodes.len() + 1],
+            nodes,
+            successors,
+            in_degree,
+            done: false,
+        }
+    }
+
+    /// Undoes the last choice, returning `false` if there was none.
+    fn backtrack(&mut self) -> bool {
+        let Some(i) = self.order.pop() else {
+            return false;
+        };
+        self.used[i] = false;
+        for &m in &self.successors[i] {
+            self.in_degree[m] += 1;
+        }
+        true
+    }
+}
+
+impl<Node> Iterator for AllTopologicalOrders<Node>
+where
+    Node: Eq + Hash + Copy,
+{
+    type Item = Vec<Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let depth = self.order.len();
+
+            if depth == self.nodes.len() {
+                let order = self.order.iter().map(|&i| self.nodes[i]).collect();
+                self.done = !self.backtrack();
+                return Some(order);
+            }
+
+            let candidate = (self.next_choice[depth]..self.nodes.len())
+                .find(|&i| !self.used[i] && self.in_degree[i] == 0);
+
+            match candidate {
+                Some(i) => {
+                    self.next_choice[depth] = i + 1;
+                    self.next_choice[depth + 1] = 0;
+                    self.used[i] = true;
+                    for &m in &self.successors[i] {
+                        self.in_degree[m] -= 1;
+                    }
+                    self.order.push(i);
+                }
+                None => self.done = !self.backtrack(),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+
+    use super::Cycle;
+    use crate::graphs::{AdjacencyList, AdjacencyMatrix};
+    use crate::{Graph, GraphIO};
+
+    static PATH: &str = "examples/data/";
+
+    /// Checks that every edge of the graph goes forward on `order`.
+    fn respects_edges<G: Graph<usize>>(graph: &G, order: &[usize]) -> bool {
+        let mut position = vec![0; graph.order()];
+        for (i, &n) in order.iter().enumerate() {
+            position[n] = i;
+        }
+        order.len() == graph.order()
+            && graph
+                .nodes()
+                .all(|n| graph.neighbors(n).all(|m| position[n] < position[m]))
+    }
+
+    /// Checks that `cycle` is a closed walk on the graph.
+    fn is_cycle<G: Graph<usize>>(graph: &G, cycle: &[usize]) -> bool {
+        !cycle.is_empty()
+            && cycle
+                .iter()
+                .zip(cycle.iter().cycle().skip(1))
+                .all(|(&n, &m)| graph.has_edge(n, m))
+    }
+
+    #[test]
+    fn topological_sort_of_dag() {
+        // 0 -> 1 -> 3
+        // |         ^
+        // v         |
+        // 2 --------+    4
+        let list = AdjacencyList(vec![vec![1, 2], vec![3], vec![3], vec![], vec![]]);
+
+        let kahn = list.topological_sort();
+        let dfs = list.topological_sort_dfs();
+
+        assert_eq!(kahn, Ok(vec![0, 4, 1, 2, 3]));
+        assert!(dfs.is_ok());
+
+        if let Ok(order) = dfs {
+            assert!(respects_edges(&list, &order));
+        }
+    }
+
+    #[test]
+    fn topological_sort_reports_cycle() {
+        let result: Result<AdjacencyList, Error> =
+            GraphIO::import_from_file(PATH.to_owned() + "DIGRAFO1.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(list) = result {
+            for sorted in [list.topological_sort(), list.topological_sort_dfs()] {
+                assert!(sorted.is_err());
+
+                if let Err(Cycle(cycle)) = sorted {
+                    assert!(is_cycle(&list, &cycle));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn two_cycle_and_self_loop() {
+        // 0 <-> 1
+        let list = AdjacencyList(vec![vec![1], vec![0]]);
+        assert_eq!(list.topological_sort_dfs(), Err(Cycle(vec![0, 1])));
+
+        // 0 -> 1 -> 1
+        let matrix = AdjacencyMatrix(vec![vec![0, 1], vec![0, 1]]);
+        assert_eq!(matrix.topological_sort(), Err(Cycle(vec![1])));
+    }
+
+    #[test]
+    fn all_topological_orders() {
+        // 0 -> 2 <- 1, 2 -> 3
+        let list = AdjacencyList(vec![vec![2], vec![2], vec![3], vec![]]);
+
+        let orders: Vec<Vec<usize>> = list.all_topological_orders().collect();
+
+        assert_eq!(orders, vec![vec![0, 1, 2, 3], vec![1, 0, 2, 3]]);
+
+        // 3 isolated nodes have all 3! orders
+        let isolated = AdjacencyList(vec![vec![]; 3]);
+        assert_eq!(isolated.all_topological_orders().count(), 6);
+
+        let cyclic = AdjacencyList(vec![vec![1], vec![0], vec![]]);
+        assert_eq!(cyclic.all_topological_orders().next(), None);
+
+        let empty = AdjacencyList::default();
+        assert_eq!(
+            empty.all_topological_orders().collect::<Vec<_>>(),
+            vec![vec![]]
+        );
+    }
+}
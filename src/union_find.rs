@@ -0,0 +1,472 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{Graph, UndirectedGraph};
+
+/// Represents a **disjoint-set forest** (union-find) over the elements `0..n`.
+///
+/// Sets are merged by size, and [`UnionFind::find_mut`] compresses the paths it walks,
+/// so any sequence of operations runs in almost linear time.
+///
+/// # Panics
+/// Every method taking an element panics if it's not below [`UnionFind::len`].
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// Creates `n` singleton sets, one for each element of `0..n`.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            sets: n,
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Returns `true` if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// Adds a new element on a singleton set and returns it.
+    pub fn add_element(&mut self) -> usize {
+        let x = self.parent.len();
+        self.parent.push(x);
+        self.size.push(1);
+        self.sets += 1;
+        x
+    }
+
+    /// Returns the representative of the set of `x`, without changing the forest.
+    pub fn find(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Returns the representative of the set of `x`, pointing every element
+    /// on the way straight to it.
+    pub fn find_mut(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        let mut current = x;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+
+    /// Merges the sets of `x` and `y`, returning `false` if they were already the same set.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.find_mut(x), self.find_mut(y));
+        if x == y {
+            return false;
+        }
+        if self.size[x] < self.size[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        self.sets -= 1;
+        true
+    }
+
+    /// Returns `true` if `x` and `y` belong to the same set.
+    pub fn same_set(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Returns the number of elements on the set of `x`.
+    pub fn set_size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+}
+
+/// Represents a **disjoint-set forest** over the elements `0..n` whose unions can be undone,
+/// as needed by offline algorithms that explore and then backtrack.
+///
+/// Sets are merged by size, but paths aren't compressed, since that would make the unions
+/// impossible to undo: [`RollbackUnionFind::find`] takes `O(log n)` time.
+///
+/// # Panics
+/// Every method taking an element panics if it's not below [`RollbackUnionFind::len`].
+#[derive(Debug, Clone)]
+pub struct RollbackUnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+    history: Vec<Option<(usize, usize)>>,
+}
+
+impl RollbackUnionFind {
+    /// Creates `n` singleton sets, one for each element of `0..n`.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            sets: n,
+            history: Vec::new(),
+        }
+    }
+
+    /// Returns the number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Returns `true` if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn set_count(&self) -> usize {
+        self.sets
+    }
+
+    /// Returns the representative of the set of `x`.
+    pub fn find(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the sets of `x` and `y`, returning `false` if they were already the same set.
+    ///
+    /// Either way, the call is recorded, so it's undone by a single [`RollbackUnionFind::rollback`].
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.find(x), self.find(y));
+        if x == y {
+            self.history.push(None);
+            return false;
+        }
+        if self.size[x] < self.size[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        self.sets -= 1;
+        self.history.push(Some((x, y)));
+        true
+    }
+
+    /// Returns `true` if `x` and `y` belong to the same set.
+    pub fn same_set(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Returns the number of elements on the set of `x`.
+    pub fn set_size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+
+    /// Returns the number of unions done so far, to be later passed to
+    /// [`RollbackUnionFind::rollback_to`].
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undoes the last union, returning `false` if there was none.
+    pub fn rollback(&mut self) -> bool {
+        let Some(entry) = self.history.pop() else {
+            return false;
+        };
+        if let Some((x, y)) = entry {
+            self.parent[y] = y;
+            self.size[x] -= self.size[y];
+            self.sets += 1;
+        }
+        true
+    }
+
+    /// Undoes every union done after `snapshot` was taken, latest first.
+    pub fn rollback_to(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            self.rollback();
+        }
+    }
+}
+
+/// Represents a graph that keeps track of its connected components as edges are added.
+///
+/// It wraps a graph `G` and implements [`Graph`] itself: every edge added through
+/// [`Graph::add_edge`] also merges the components of its ends on a [`UnionFind`], so
+/// [`IncrementalConnectivity::same_component`] is answered in almost constant time.
+/// Edges are followed on both directions, so on a digraph the components are the weakly
+/// connected ones.
+///
+/// Removals can't be undone on a union-find, so removing a node or an edge
+/// rebuilds it from the whole graph in linear time.
+#[derive(Debug, Clone)]
+pub struct IncrementalConnectivity<Node, G> {
+    graph: G,
+    index: HashMap<Node, usize>,
+    sets: UnionFind,
+    merges: usize,
+}
+
+impl<Node, G> IncrementalConnectivity<Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    /// Wraps `graph`, labelling the components of the edges it already has.
+    pub fn new(graph: G) -> Self {
+        let mut connectivity = Self {
+            graph,
+            index: HashMap::new(),
+            sets: UnionFind::new(0),
+            merges: 0,
+        };
+        connectivity.rebuild();
+        connectivity
+    }
+
+    /// Returns a reference to the wrapped graph.
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /// Unwraps the graph.
+    pub fn into_inner(self) -> G {
+        self.graph
+    }
+
+    /// Returns `true` if there's a path between `n` and `m`, ignoring the direction of edges.
+    ///
+    /// A node is always on the same component as itself, as long as it belongs to the graph.
+    pub fn same_component(&self, n: Node, m: Node) -> bool {
+        match (self.index.get(&n), self.index.get(&m)) {
+            (Some(&i), Some(&j)) => self.sets.same_set(i, j),
+            _ => n == m && self.graph.nodes().any(|node| node == n),
+        }
+    }
+
+    /// Returns the number of connected components of the graph.
+    pub fn component_count(&self) -> usize {
+        self.graph.order() - self.merges
+    }
+
+    /// Returns the element of `node` on the union-find, adding it if it's not there yet.
+    fn element(&mut self, node: Node) -> usize {
+        *self
+            .index
+            .entry(node)
+            .or_insert_with(|| self.sets.add_element())
+    }
+
+    /// Merges the components of `n` and `m`, if the graph has an edge between them.
+    fn join(&mut self, n: Node, m: Node) {
+        if self.graph.has_edge(n, m) {
+            let (i, j) = (self.element(n), self.element(m));
+            if self.sets.union(i, j) {
+                self.merges += 1;
+            }
+        }
+    }
+
+    /// Labels the components again, from the current edges of the graph.
+    fn rebuild(&mut self) {
+        self.index.clear();
+        self.sets = UnionFind::new(0);
+        self.merges = 0;
+
+        let edges: Vec<(Node, Node)> = self
+            .graph
+            .nodes()
+            .flat_map(|n| self.graph.neighbors(n).map(move |m| (n, m)))
+            .collect();
+        for (n, m) in edges {
+            self.join(n, m);
+        }
+    }
+}
+
+impl<Node, G> Graph<Node> for IncrementalConnectivity<Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+{
+    fn new_empty() -> Self {
+        Self::new(G::new_empty())
+    }
+
+    fn order(&self) -> usize {
+        self.graph.order()
+    }
+
+    fn size(&self) -> usize {
+        self.graph.size()
+    }
+
+    fn node_degrees(&self, n: Node) -> (usize, usize) {
+        self.graph.node_degrees(n)
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = Node> {
+        self.graph.nodes()
+    }
+
+    fn add_node(&mut self, n: Node) {
+        self.graph.add_node(n);
+    }
+
+    fn remove_node(&mut self, n: Node) {
+        self.graph.remove_node(n);
+        self.rebuild();
+    }
+
+    fn add_edge(&mut self, n: Node, m: Node) {
+        self.graph.add_edge(n, m);
+        self.join(n, m);
+    }
+
+    fn remove_edge(&mut self, n: Node, m: Node) {
+        self.graph.remove_edge(n, m);
+        self.rebuild();
+    }
+
+    type Neighbors<'a>
+        = G::Neighbors<'a>
+    where
+        Self: 'a,
+        Node: 'a;
+
+    fn neighbors<'a>(&'a self, n: Node) -> Self::Neighbors<'a> {
+        self.graph.neighbors(n)
+    }
+
+    fn underlying_graph(&self) -> Self {
+        Self::new(self.graph.underlying_graph())
+    }
+}
+
+impl<Node, G> UndirectedGraph<Node> for IncrementalConnectivity<Node, G>
+where
+    Node: Eq + Hash + Copy,
+    G: UndirectedGraph<Node>,
+{
+    fn undirected_size(&self) -> usize {
+        self.graph.undirected_size()
+    }
+
+    fn undirected_node_degree(&self, n: Node) -> usize {
+        self.graph.undirected_node_degree(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IncrementalConnectivity, RollbackUnionFind, UnionFind};
+    use crate::graphs::{AdjacencyList, AdjacencyMatrix};
+    use crate::{Graph, UndirectedGraph};
+
+    #[test]
+    fn union_find_merges_sets() {
+        let mut sets = UnionFind::new(6);
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert_eq!(sets.set_count(), 3);
+        assert_eq!(sets.set_size(3), 4);
+        assert!(sets.same_set(0, 2));
+        assert!(!sets.same_set(0, 4));
+        assert_eq!(sets.find_mut(2), sets.find(0));
+
+        let x = sets.add_element();
+        assert_eq!(x, 6);
+        assert!(sets.union(x, 5));
+        assert_eq!(sets.set_count(), 3);
+        assert_eq!(sets.len(), 7);
+    }
+
+    #[test]
+    fn rollback_undoes_unions() {
+        let mut sets = RollbackUnionFind::new(5);
+        sets.union(0, 1);
+        let snapshot = sets.snapshot();
+
+        sets.union(1, 2);
+        sets.union(0, 2);
+        sets.union(3, 4);
+        assert_eq!(sets.set_count(), 2);
+        assert_eq!(sets.set_size(0), 3);
+
+        assert!(sets.rollback());
+        assert!(!sets.same_set(3, 4));
+        assert!(sets.same_set(0, 2));
+
+        sets.rollback_to(snapshot);
+        assert_eq!(sets.set_count(), 4);
+        assert!(sets.same_set(0, 1));
+        assert!(!sets.same_set(1, 2));
+        assert_eq!(sets.set_size(1), 2);
+
+        sets.rollback_to(0);
+        assert!(!sets.rollback());
+        assert_eq!(sets.set_count(), 5);
+    }
+
+    #[test]
+    fn incremental_connectivity_follows_edges() {
+        // 0 -- 1    2 -- 3    4
+        let mut graph = AdjacencyList(vec![vec![]; 5]);
+        graph.add_undirected_edge(0, 1);
+        graph.add_undirected_edge(2, 3);
+
+        let mut connectivity = IncrementalConnectivity::new(graph);
+        assert_eq!(connectivity.component_count(), 3);
+        assert!(connectivity.same_component(0, 1));
+        assert!(!connectivity.same_component(1, 2));
+        assert!(connectivity.same_component(4, 4));
+        assert!(!connectivity.same_component(5, 5));
+
+        connectivity.add_undirected_edge(1, 2);
+        assert!(connectivity.same_component(0, 3));
+        assert_eq!(connectivity.component_count(), 2);
+
+        connectivity.add_node(5);
+        connectivity.add_edge(5, 4);
+        assert!(connectivity.same_component(4, 5));
+        assert_eq!(connectivity.component_count(), 2);
+
+        connectivity.remove_undirected_edge(1, 2);
+        assert!(!connectivity.same_component(0, 3));
+        assert_eq!(connectivity.component_count(), 3);
+        assert_eq!(
+            connectivity.component_count(),
+            connectivity.inner().weakly_connected_components().count()
+        );
+    }
+
+    #[test]
+    fn incremental_connectivity_ignores_missing_nodes() {
+        let mut connectivity = IncrementalConnectivity::new(AdjacencyMatrix(vec![vec![0; 2]; 2]));
+
+        connectivity.add_edge(0, 7);
+        assert!(!connectivity.same_component(0, 7));
+        assert_eq!(connectivity.component_count(), 2);
+
+        connectivity.add_edge(1, 0);
+        assert!(connectivity.same_component(0, 1));
+        assert_eq!(connectivity.component_count(), 1);
+    }
+}
@@ -19,7 +19,7 @@ pub fn print_matrix(m: &AdjacencyMatrix) {
 }
 
 pub fn print_incidence_matrix(m: &IncidenceMatrix) {
-    for row in &m.0 {
+    for row in m.rows() {
         print!("[ ");
         for col in row {
             print!("{col} ");
@@ -0,0 +1,299 @@
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::Graph;
+
+/// Tells a traversal how to go on after a [`Visitor`] hook.
+///
+/// # Variants
+/// - `Continue`: Goes on with the traversal as usual.
+/// - `Prune`: Skips what the hook is about: the neighbors of a discovered node, the edge
+///   being examined or the child of a tree edge. It's the same as `Continue` on the other hooks.
+/// - `Break(B)`: Stops the traversal, which returns the value `B`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control<B> {
+    Continue,
+    Prune,
+    Break(B),
+}
+
+/// Defines the hooks called by [`Graph::dfs_with`] and [`Graph::bfs_with`] during a traversal,
+/// in the style of the visitors of the Boost Graph Library.
+///
+/// Every hook does nothing and returns [`Control::Continue`] by default,
+/// so only the relevant ones need to be implemented.
+///
+/// # Type Parameters
+/// - `Node`: The type used to represent graph nodes.
+pub trait Visitor<Node> {
+    /// The value returned by a traversal stopped with [`Control::Break`].
+    type Break;
+
+    /// Called when `node` is reached for the first time.
+    ///
+    /// Returning [`Control::Prune`] keeps its neighbors from being explored.
+    fn discover(&mut self, _node: Node) -> Control<Self::Break> {
+        Control::Continue
+    }
+
+    /// Called on every edge `(u -> v)` leaving an explored node, before it's classified.
+    ///
+    /// Returning [`Control::Prune`] skips the edge.
+    fn examine_edge(&mut self, _u: Node, _v: Node) -> Control<Self::Break> {
+        Control::Continue
+    }
+
+    /// Called on an edge `(u -> v)` that reaches `v` for the first time, before it's discovered.
+    ///
+    /// Returning [`Control::Prune`] leaves `v` undiscovered, though it can still be
+    /// reached through another edge.
+    fn tree_edge(&mut self, _u: Node, _v: Node) -> Control<Self::Break> {
+        Control::Continue
+    }
+
+    /// Called on an edge `(u -> v)` to an ancestor `v` of `u` during a DFS.
+    fn back_edge(&mut self, _u: Node, _v: Node) -> Control<Self::Break> {
+        Control::Continue
+    }
+
+    /// Called on any other edge `(u -> v)` to an already discovered node: forward and cross
+    /// edges during a DFS, and every non-tree edge during a BFS.
+    fn non_tree_edge(&mut self, _u: Node, _v: Node) -> Control<Self::Break> {
+        Control::Continue
+    }
+
+    /// Called when all neighbors of `node` were explored (or pruned).
+    fn finish(&mut self, _node: Node) -> Control<Self::Break> {
+        Control::Continue
+    }
+}
+
+/// Returns from the enclosing traversal on [`Control::Break`],
+/// or evaluates to whether the hook asked for a prune.
+macro_rules! pruned {
+    ($control:expr) => {
+        match $control {
+            Control::Continue => false,
+            Control::Prune => true,
+            Control::Break(value) => return Some(value),
+        }
+    };
+}
+
+/// Runs a DFS from `start`, calling the hooks of `visitor` along the way.
+pub(crate) fn dfs_with<Node, G, V>(graph: &G, start: Node, visitor: &mut V) -> Option<V::Break>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+    V: Visitor<Node>,
+{
+    let mut visited: HashSet<Node> = HashSet::with_capacity(graph.order());
+    let mut active: HashSet<Node> = HashSet::new();
+    let mut stack: Vec<(Node, G::Neighbors<'_>)> = Vec::new();
+
+    visited.insert(start);
+    if pruned!(visitor.discover(start)) {
+        pruned!(visitor.finish(start));
+        return None;
+    }
+    active.insert(start);
+    stack.push((start, graph.neighbors(start)));
+
+    while let Some((u, neighbors)) = stack.last_mut() {
+        let u = *u;
+        let Some(v) = neighbors.next() else {
+            stack.pop();
+            active.remove(&u);
+            pruned!(visitor.finish(u));
+            continue;
+        };
+
+        if pruned!(visitor.examine_edge(u, v)) {
+            continue;
+        }
+
+        if !visited.contains(&v) {
+            if pruned!(visitor.tree_edge(u, v)) {
+                continue;
+            }
+            visited.insert(v);
+            if pruned!(visitor.discover(v)) {
+                pruned!(visitor.finish(v));
+            } else {
+                active.insert(v);
+                stack.push((v, graph.neighbors(v)));
+            }
+        } else if active.contains(&v) {
+            pruned!(visitor.back_edge(u, v));
+        } else {
+            pruned!(visitor.non_tree_edge(u, v));
+        }
+    }
+    None
+}
+
+/// Runs a BFS from `start`, calling the hooks of `visitor` along the way.
+pub(crate) fn bfs_with<Node, G, V>(graph: &G, start: Node, visitor: &mut V) -> Option<V::Break>
+where
+    Node: Eq + Hash + Copy,
+    G: Graph<Node>,
+    V: Visitor<Node>,
+{
+    let mut visited: HashSet<Node> = HashSet::with_capacity(graph.order());
+    let mut queue: VecDeque<Node> = VecDeque::new();
+
+    visited.insert(start);
+    if pruned!(visitor.discover(start)) {
+        pruned!(visitor.finish(start));
+        return None;
+    }
+    queue.push_back(start);
+
+    while let Some(u) = queue.pop_front() {
+        for v in graph.neighbors(u) {
+            if pruned!(visitor.examine_edge(u, v)) {
+                continue;
+            }
+
+            if !visited.contains(&v) {
+                if pruned!(visitor.tree_edge(u, v)) {
+                    continue;
+                }
+                visited.insert(v);
+                if pruned!(visitor.discover(v)) {
+                    pruned!(visitor.finish(v));
+                } else {
+                    queue.push_back(v);
+                }
+            } else {
+                pruned!(visitor.non_tree_edge(u, v));
+            }
+        }
+        pruned!(visitor.finish(u));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{Control, Visitor};
+    use crate::Graph;
+    use crate::graphs::AdjacencyList;
+
+    /// Records the order of the hooks and stops when `target` is discovered.
+    struct Recorder {
+        target: usize,
+        prune: usize,
+        discovered: Vec<usize>,
+        finished: Vec<usize>,
+        back_edges: Vec<(usize, usize)>,
+    }
+
+    impl Recorder {
+        fn new(target: usize, prune: usize) -> Self {
+            Self {
+                target,
+                prune,
+                discovered: vec![],
+                finished: vec![],
+                back_edges: vec![],
+            }
+        }
+    }
+
+    impl Visitor<usize> for Recorder {
+        type Break = usize;
+
+        fn discover(&mut self, node: usize) -> Control<usize> {
+            self.discovered.push(node);
+            if node == self.target {
+                Control::Break(node)
+            } else if node == self.prune {
+                Control::Prune
+            } else {
+                Control::Continue
+            }
+        }
+
+        fn back_edge(&mut self, u: usize, v: usize) -> Control<usize> {
+            self.back_edges.push((u, v));
+            Control::Continue
+        }
+
+        fn finish(&mut self, node: usize) -> Control<usize> {
+            self.finished.push(node);
+            Control::Continue
+        }
+    }
+
+    #[test]
+    fn dfs_with_break_and_prune() {
+        // 0 -> 1 -> 2 -> 0
+        // |    '--> 3
+        // '-> 4 -> 5
+        let g = AdjacencyList(vec![
+            vec![1, 4],
+            vec![2, 3],
+            vec![0],
+            vec![],
+            vec![5],
+            vec![],
+        ]);
+
+        let mut full = Recorder::new(usize::MAX, usize::MAX);
+        assert_eq!(g.dfs_with(0, &mut full), None);
+        assert_eq!(full.discovered, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(full.finished, vec![2, 3, 1, 5, 4, 0]);
+        assert_eq!(full.back_edges, vec![(2, 0)]);
+
+        let mut pruned = Recorder::new(usize::MAX, 1);
+        assert_eq!(g.dfs_with(0, &mut pruned), None);
+        assert_eq!(pruned.discovered, vec![0, 1, 4, 5]);
+
+        let mut stopped = Recorder::new(3, usize::MAX);
+        assert_eq!(g.dfs_with(0, &mut stopped), Some(3));
+        assert_eq!(stopped.discovered, vec![0, 1, 2, 3]);
+        assert_eq!(stopped.finished, vec![2]);
+    }
+
+    /// Explores nodes up to `max_depth` edges away from the start.
+    struct DepthLimit {
+        max_depth: usize,
+        depth: HashMap<usize, usize>,
+    }
+
+    impl Visitor<usize> for DepthLimit {
+        type Break = ();
+
+        fn tree_edge(&mut self, u: usize, v: usize) -> Control<()> {
+            let depth = self.depth.get(&u).copied().unwrap_or(0) + 1;
+            if depth > self.max_depth {
+                return Control::Prune;
+            }
+            self.depth.insert(v, depth);
+            Control::Continue
+        }
+    }
+
+    #[test]
+    fn bfs_with_depth_limit() {
+        // 0 -> 1 -> 2 -> 3 -> 4
+        let g = AdjacencyList(vec![vec![1], vec![2], vec![3], vec![4], vec![]]);
+
+        let mut limit = DepthLimit {
+            max_depth: 2,
+            depth: HashMap::new(),
+        };
+
+        assert_eq!(g.bfs_with(0, &mut limit), None);
+        assert_eq!(limit.depth.get(&2), Some(&2));
+        assert_eq!(limit.depth.get(&3), None);
+
+        let mut stopped = Recorder::new(2, usize::MAX);
+        assert_eq!(g.bfs_with(0, &mut stopped), Some(2));
+        assert_eq!(stopped.discovered, vec![0, 1, 2]);
+        assert_eq!(stopped.finished, vec![0]);
+    }
+}
@@ -0,0 +1,266 @@
+use std::str::FromStr;
+
+use crate::all_pairs::{self, AllPairsShortestPaths};
+use crate::graph_io::{UndirectedGraphIO, WeightedGraphIO};
+use crate::graphs::AdjacencyList;
+use crate::shortest_paths::NegativeCycle;
+use crate::weighted_graph::{Weight, WeightedGraph};
+use crate::{Graph, GraphIO, UndirectedGraph};
+
+/// Represents a weighted graph using an adjacency list.
+/// Each index in the inner vector corresponds to a node, and the vector at that index
+/// contains the neighbors of that node together with the weight of the edge reaching them.
+#[derive(Debug, Clone)]
+pub struct WeightedAdjacencyList<W>(pub Vec<Vec<(usize, W)>>);
+
+impl<W: Weight> WeightedAdjacencyList<W> {
+    /// Returns the same graph as an [`AdjacencyList`], dropping every weight.
+    pub fn unweighted(&self) -> AdjacencyList {
+        AdjacencyList(
+            self.0
+                .iter()
+                .map(|neighbors| neighbors.iter().map(|&(m, _)| m).collect())
+                .collect(),
+        )
+    }
+
+    /// Computes the shortest paths between every pair of nodes using **Johnson's algorithm**,
+    /// which accepts negative weights and suits sparse graphs better than Floyd-Warshall.
+    ///
+    /// # Returns
+    /// The distance and next-hop matrices, or a [`NegativeCycle`] if there's one.
+    pub fn johnson(&self) -> Result<AllPairsShortestPaths<W>, NegativeCycle<usize>> {
+        all_pairs::johnson(self)
+    }
+}
+
+impl<W> Default for WeightedAdjacencyList<W> {
+    fn default() -> Self {
+        WeightedAdjacencyList(vec![])
+    }
+}
+
+impl<W: Weight> Graph<usize> for WeightedAdjacencyList<W> {
+    fn new_empty() -> Self {
+        WeightedAdjacencyList(vec![])
+    }
+
+    fn order(&self) -> usize {
+        self.0.len()
+    }
+
+    fn size(&self) -> usize {
+        self.0.iter().map(|neighbors| neighbors.len()).sum()
+    }
+
+    fn node_degrees(&self, n: usize) -> (usize, usize) {
+        let out_deg = self.0.get(n).map_or(0, |neighbors| neighbors.len());
+        let in_deg = self
+            .0
+            .iter()
+            .filter(|neighbors| neighbors.iter().any(|&(m, _)| m == n))
+            .count();
+        (in_deg, out_deg)
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = usize> {
+        0..self.order()
+    }
+
+    fn add_node(&mut self, _n: usize) {
+        self.0.push(Vec::new());
+    }
+
+    fn remove_node(&mut self, n: usize) {
+        if n < self.0.len() {
+            self.0.remove(n);
+            for neighbors in self.0.iter_mut() {
+                neighbors.retain(|&(x, _)| x != n);
+                for (x, _) in neighbors.iter_mut() {
+                    if *x > n {
+                        *x -= 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_edge(&mut self, n: usize, m: usize) {
+        if !self.has_edge(n, m) {
+            self.add_weighted_edge(n, m, W::one());
+        }
+    }
+
+    fn remove_edge(&mut self, n: usize, m: usize) {
+        if let Some(edges) = self.0.get_mut(n) {
+            edges.retain(|&(x, _)| x != m);
+        }
+    }
+
+    type Neighbors<'a>
+        = std::iter::Map<std::slice::Iter<'a, (usize, W)>, fn(&'a (usize, W)) -> usize>
+    where
+        W: 'a;
+
+    fn neighbors<'a>(&'a self, n: usize) -> Self::Neighbors<'a> {
+        fn node<W>(&(m, _): &(usize, W)) -> usize {
+            m
+        }
+        match self.0.get(n) {
+            Some(edges) => edges.iter().map(node),
+            None => [].iter().map(node),
+        }
+    }
+
+    fn underlying_graph(&self) -> Self {
+        let mut list = WeightedAdjacencyList(vec![Vec::new(); self.0.len()]);
+
+        for (idx_r, row) in self.0.iter().enumerate() {
+            for &(col, w) in row.iter() {
+                if !list.has_edge(idx_r, col) {
+                    list.add_undirected_weighted_edge(idx_r, col, w);
+                }
+            }
+        }
+        list
+    }
+}
+
+impl<W: Weight> WeightedGraph<usize, W> for WeightedAdjacencyList<W> {
+    fn add_weighted_edge(&mut self, n: usize, m: usize, w: W) {
+        if m >= self.0.len() {
+            return;
+        }
+        if let Some(n_edges) = self.0.get_mut(n) {
+            match n_edges.iter_mut().find(|(x, _)| *x == m) {
+                Some((_, weight)) => *weight = w,
+                None => n_edges.push((m, w)),
+            }
+        }
+    }
+
+    fn edge_weight(&self, n: usize, m: usize) -> Option<W> {
+        self.0
+            .get(n)?
+            .iter()
+            .find_map(|&(x, w)| (x == m).then_some(w))
+    }
+
+    fn weighted_neighbors<'a>(&'a self, n: usize) -> impl Iterator<Item = (usize, W)> + 'a
+    where
+        usize: 'a,
+    {
+        self.0.get(n).into_iter().flatten().copied()
+    }
+}
+
+impl<W: Weight> UndirectedGraph<usize> for WeightedAdjacencyList<W> {
+    fn undirected_size(&self) -> usize {
+        self.unweighted().undirected_size()
+    }
+
+    fn undirected_node_degree(&self, node: usize) -> usize {
+        self.0
+            .get(node)
+            .map(|neighbors| neighbors.len())
+            .unwrap_or(0)
+    }
+}
+
+impl<W: Weight> GraphIO<usize> for WeightedAdjacencyList<W> {}
+impl<W: Weight> UndirectedGraphIO<usize> for WeightedAdjacencyList<W> {}
+impl<W: Weight + FromStr> WeightedGraphIO<usize, W> for WeightedAdjacencyList<W> {}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Error;
+
+    use super::*;
+
+    static PATH: &str = "examples/data/";
+
+    #[test]
+    fn new_weighted_digraph() {
+        let result: Result<WeightedAdjacencyList<i64>, Error> =
+            WeightedGraphIO::import_weighted_from_file(PATH.to_owned() + "DIGRAFO_PONDERADO.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(list) = result {
+            assert_eq!(list.order(), 6);
+            assert_eq!(list.size(), 9);
+            assert_eq!(list.edge_weight(0, 5), Some(14));
+            assert_eq!(list.edge_weight(5, 0), None);
+        }
+    }
+
+    #[test]
+    fn new_weighted_undirected_graph_without_weights() {
+        // Lines without a third column get the unit weight.
+        let result: Result<WeightedAdjacencyList<f64>, Error> =
+            WeightedGraphIO::import_undirected_weighted_from_file(PATH.to_owned() + "GRAFO_2.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(list) = result {
+            assert_eq!(list.order(), 11);
+            assert_eq!(list.undirected_size(), 13);
+            assert_eq!(list.edge_weight(1, 0), Some(1.0));
+        }
+    }
+
+    #[test]
+    fn unweighted_import_ignores_weights() {
+        let result: Result<AdjacencyList, Error> =
+            GraphIO::import_from_file(PATH.to_owned() + "DIGRAFO_PONDERADO.txt");
+
+        assert!(result.is_ok());
+
+        if let Ok(list) = result {
+            assert_eq!(list.size(), 9);
+            assert!(list.has_edge(0, 5));
+        }
+    }
+
+    #[test]
+    fn add_and_replace_weighted_edges() {
+        // Graph: 0 -(3)-> 1 -(1)-> 2
+        let mut list: WeightedAdjacencyList<i32> = WeightedAdjacencyList::default();
+        list.add_node(0);
+        list.add_node(1);
+        list.add_node(2);
+        list.add_weighted_edge(0, 1, 5);
+        list.add_weighted_edge(0, 1, 3);
+        list.add_edge(1, 2);
+        list.add_edge(1, 2);
+        list.add_weighted_edge(2, 7, 1);
+
+        assert_eq!(list.size(), 2);
+        assert_eq!(list.edge_weight(0, 1), Some(3));
+        assert_eq!(list.edge_weight(1, 2), Some(1));
+        assert_eq!(list.weighted_neighbors(0).collect::<Vec<_>>(), vec![(1, 3)]);
+    }
+
+    #[test]
+    fn remove_node_keeps_weights() {
+        // Graph: 0 -(2)-> 1 -(4)-> 2 -(8)-> 0
+        let mut list = WeightedAdjacencyList(vec![vec![(1, 2)], vec![(2, 4)], vec![(0, 8)]]);
+        list.remove_node(1);
+
+        // Current graph: 0 <-(8)- 1
+        assert_eq!(list.0, vec![vec![], vec![(0, 8)]]);
+        assert_eq!(list.node_degrees(0), (1, 0));
+    }
+
+    #[test]
+    fn weighted_underlying_graph() {
+        // Graph: 0 -(2)-> 1 <-(5)- 2
+        let list = WeightedAdjacencyList(vec![vec![(1, 2)], vec![], vec![(1, 5)]]);
+        let underlying = list.underlying_graph();
+
+        assert_eq!(underlying.edge_weight(1, 0), Some(2));
+        assert_eq!(underlying.edge_weight(1, 2), Some(5));
+        assert_eq!(underlying.undirected_size(), 2);
+        assert!(underlying.connected());
+    }
+}
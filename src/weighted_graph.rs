@@ -0,0 +1,289 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::blossom;
+use crate::flow::{self, MaxFlow};
+use crate::matching::Matching;
+use crate::min_cost_flow::{self, MinCostFlow};
+use crate::mst::{self, SpanningForest};
+use crate::shortest_paths::{self, AStarSearch, NegativeCycle, ShortestPaths};
+use crate::{Graph, UndirectedGraph};
+
+/// Defines the numeric operations needed from an edge weight.
+///
+/// It's implemented for every primitive integer and floating point type.
+/// Weights only need a partial order, so floating point weights can be used,
+/// but comparing a `NaN` weight has no meaningful result. Algorithms that sort
+/// weights use [`Weight::total_cmp`] instead, so a `NaN` can't break the sort.
+pub trait Weight: Copy + PartialOrd + Debug + Add<Output = Self> + Sub<Output = Self> {
+    /// Returns the additive identity, the weight of an empty path.
+    fn zero() -> Self;
+
+    /// Returns the unit weight, used for edges added without an explicit weight.
+    fn one() -> Self;
+
+    /// Compares two weights with a total order that agrees with [`PartialOrd`]
+    /// wherever it's defined. Floating point weights follow [`f64::total_cmp`].
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! impl_weight {
+    ($zero:literal, $one:literal, $cmp:ident: $($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    $zero
+                }
+
+                fn one() -> Self {
+                    $one
+                }
+
+                fn total_cmp(&self, other: &Self) -> Ordering {
+                    <$t>::$cmp(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_weight!(0, 1, cmp: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_weight!(0.0, 1.0, total_cmp: f32, f64);
+
+/// Defines a **weighted directed graph**, where each edge carries a weight of type `W`.
+///
+/// Extends [`Graph`], so every unweighted operation still works on the graph:
+/// [`Graph::add_edge`] adds an edge with the unit weight ([`Weight::one`]).
+///
+/// # Type Parameters
+/// - `Node`: The type used to represent graph nodes.
+/// - `W`: The type of the edge weights.
+pub trait WeightedGraph<Node: Eq + Hash + Copy, W: Weight>: Graph<Node> {
+    /// Adds a **directed edge** from node `n` to node `m` with weight `w`.
+    ///
+    /// If the edge already exists, its weight is replaced by `w`.
+    /// If either node does not exist, this operation has no effect.
+    fn add_weighted_edge(&mut self, n: Node, m: Node, w: W);
+
+    /// Returns the weight of the directed edge from node `n` to node `m`,
+    /// or `None` if there's no such edge.
+    fn edge_weight(&self, n: Node, m: Node) -> Option<W>;
+
+    /// Returns an iterator over the **neighbors** of a given node, together with
+    /// the weight of the edge that reaches each of them.
+    fn weighted_neighbors<'a>(&'a self, n: Node) -> impl Iterator<Item = (Node, W)> + 'a
+    where
+        Node: 'a,
+    {
+        self.neighbors(n)
+            .filter_map(move |m| self.edge_weight(n, m).map(|w| (m, w)))
+    }
+
+    /// Adds an **undirected edge** `(n <-> m)` with weight `w`.
+    ///
+    /// Internally, this adds both directed edges `(n -> m)` and `(m -> n)` with the same weight.
+    fn add_undirected_weighted_edge(&mut self, n: Node, m: Node, w: W) {
+        self.add_weighted_edge(n, m, w);
+        self.add_weighted_edge(m, n, w);
+    }
+
+    /// Computes the shortest paths from `source` to every reachable node
+    /// using **Dijkstra's algorithm**.
+    ///
+    /// The search walks the graph through [`WeightedGraph::weighted_neighbors`], so it works
+    /// on every weighted representation. All weights must be non-negative.
+    /// The plain [`AdjacencyList`](crate::graphs::AdjacencyList) stores no weights and has no
+    /// Dijkstra; on unit weights, [`Graph::shortest_path`] finds the same paths by BFS.
+    fn dijkstra(&self, source: Node) -> ShortestPaths<Node, W>
+    where
+        Self: Sized,
+    {
+        shortest_paths::dijkstra(self, [source], None)
+    }
+
+    /// Computes the shortest path from `source` to `target` using **Dijkstra's algorithm**.
+    ///
+    /// The search stops as soon as `target` is settled, so the result only holds
+    /// the nodes settled up to then, all with their final distance.
+    fn dijkstra_to(&self, source: Node, target: Node) -> ShortestPaths<Node, W>
+    where
+        Self: Sized,
+    {
+        shortest_paths::dijkstra(self, [source], Some(target))
+    }
+
+    /// Computes the shortest paths from the closest of many `sources` to every reachable
+    /// node using **Dijkstra's algorithm**.
+    ///
+    /// Each path rebuilt from the result starts at the source closest to its target.
+    fn dijkstra_multi_source(
+        &self,
+        sources: impl IntoIterator<Item = Node>,
+    ) -> ShortestPaths<Node, W>
+    where
+        Self: Sized,
+    {
+        shortest_paths::dijkstra(self, sources, None)
+    }
+
+    /// Computes the shortest paths from `source` to every reachable node
+    /// using the **Bellman-Ford algorithm**, which accepts negative weights.
+    ///
+    /// # Returns
+    /// The shortest paths, or a [`NegativeCycle`] reachable from `source` if there's one,
+    /// since no shortest path is defined through it.
+    fn bellman_ford(&self, source: Node) -> Result<ShortestPaths<Node, W>, NegativeCycle<Node>>
+    where
+        Self: Sized,
+    {
+        shortest_paths::bellman_ford(self, source)
+    }
+
+    /// Computes the shortest paths from `source` to every reachable node using the
+    /// **Shortest Path Faster Algorithm** (SPFA), a queue-based variant of Bellman-Ford.
+    ///
+    /// It's usually faster than [`WeightedGraph::bellman_ford`] on sparse graphs,
+    /// with the same worst case and the same result.
+    fn spfa(&self, source: Node) -> Result<ShortestPaths<Node, W>, NegativeCycle<Node>>
+    where
+        Self: Sized,
+    {
+        shortest_paths::spfa(self, source)
+    }
+
+    /// Searches a shortest path from `start` to `goal` using the **A\* algorithm**,
+    /// following the weight of each edge.
+    ///
+    /// To count every edge as the unit weight instead, see [`Graph::unweighted_astar`].
+    ///
+    /// # Arguments
+    /// * `heuristic` - Estimates the distance from a node to `goal`. The path found is a
+    ///   shortest one as long as it never overestimates that distance.
+    fn astar(&self, start: Node, goal: Node, heuristic: impl Fn(Node) -> W) -> AStarSearch<Node, W>
+    where
+        Self: Sized,
+    {
+        shortest_paths::astar(start, goal, |n| self.weighted_neighbors(n), heuristic)
+    }
+
+    /// Computes a **minimum spanning forest** of the undirected graph with
+    /// **Kruskal's algorithm**, using a union-find to skip the edges that close a cycle.
+    fn kruskal(&self) -> SpanningForest<Node, W>
+    where
+        Self: UndirectedGraph<Node> + Sized,
+    {
+        mst::kruskal(self)
+    }
+
+    /// Computes a **minimum spanning forest** of the undirected graph with
+    /// **Prim's algorithm**, using a binary heap of the edges leaving each tree.
+    fn prim(&self) -> SpanningForest<Node, W>
+    where
+        Self: UndirectedGraph<Node> + Sized,
+    {
+        mst::prim(self)
+    }
+
+    /// Computes a **minimum spanning forest** of the undirected graph with
+    /// **Borůvka's algorithm**, joining every component to its closest neighbor on each round.
+    fn boruvka(&self) -> SpanningForest<Node, W>
+    where
+        Self: UndirectedGraph<Node> + Sized,
+    {
+        mst::boruvka(self)
+    }
+
+    /// Computes a **maximum flow** from `source` to `sink` with the **Edmonds-Karp algorithm**,
+    /// taking each edge weight as its capacity.
+    ///
+    /// # Returns
+    /// The flow value, the flow through each edge and a minimum cut, or `None` if
+    /// `source` and `sink` aren't two distinct nodes of the graph.
+    fn edmonds_karp(&self, source: Node, sink: Node) -> Option<MaxFlow<Node, W>>
+    where
+        Self: Sized,
+    {
+        flow::edmonds_karp(self, source, sink)
+    }
+
+    /// Computes a **maximum flow** from `source` to `sink` with **Dinic's algorithm**,
+    /// taking each edge weight as its capacity.
+    ///
+    /// It gives the same result as [`WeightedGraph::edmonds_karp`], usually much faster.
+    fn dinic(&self, source: Node, sink: Node) -> Option<MaxFlow<Node, W>>
+    where
+        Self: Sized,
+    {
+        flow::dinic(self, source, sink)
+    }
+
+    /// Computes a **maximum flow** from `source` to `sink` with the **push-relabel algorithm**,
+    /// taking each edge weight as its capacity.
+    ///
+    /// It gives the same result as [`WeightedGraph::edmonds_karp`], and suits dense graphs best.
+    fn push_relabel(&self, source: Node, sink: Node) -> Option<MaxFlow<Node, W>>
+    where
+        Self: Sized,
+    {
+        flow::push_relabel(self, source, sink)
+    }
+
+    /// Computes a **minimum cost maximum flow** from `source` to `sink` with
+    /// **successive shortest paths**, taking each edge weight as its capacity and
+    /// `cost[&(u, v)]` as the cost of each unit of flow through the edge `(u -> v)`.
+    ///
+    /// Costs have their own type `C`, which must be signed, as pushing flow back refunds
+    /// its cost. Every edge of the graph needs an entry on `cost`.
+    ///
+    /// # Returns
+    /// The flow through each edge and its total cost, or `None` if `source` and `sink`
+    /// aren't two distinct nodes of the graph, if an edge has no cost, if a cycle has
+    /// negative total cost, or if the flow through an edge doesn't fit in `C`.
+    fn successive_shortest_paths<C>(
+        &self,
+        source: Node,
+        sink: Node,
+        cost: &HashMap<(Node, Node), C>,
+    ) -> Option<MinCostFlow<Node, W, C>>
+    where
+        Self: Sized,
+        C: Weight + Mul<Output = C> + Neg<Output = C> + TryFrom<W>,
+    {
+        min_cost_flow::successive_shortest_paths(self, source, sink, cost)
+    }
+
+    /// Computes a **minimum cost maximum flow** from `source` to `sink` by **cycle cancelling**,
+    /// with the same capacities and costs as [`WeightedGraph::successive_shortest_paths`].
+    ///
+    /// It's slower, but also accepts cycles with negative total cost. It only terminates
+    /// for sure on integer capacities: with fractional ones, cycles of ever smaller
+    /// capacity may be cancelled forever.
+    fn cycle_cancelling<C>(
+        &self,
+        source: Node,
+        sink: Node,
+        cost: &HashMap<(Node, Node), C>,
+    ) -> Option<MinCostFlow<Node, W, C>>
+    where
+        Self: Sized,
+        C: Weight + Mul<Output = C> + Neg<Output = C> + TryFrom<W>,
+    {
+        min_cost_flow::cycle_cancelling(self, source, sink, cost)
+    }
+
+    /// Computes a **maximum weight matching** of the undirected graph with the
+    /// primal-dual blossom algorithm, on `O(n³)` time.
+    ///
+    /// Edges without a positive weight are left out. Each matched pair is given
+    /// in [`Graph::nodes`] order.
+    fn maximum_weight_matching(&self) -> Matching<Node>
+    where
+        Self: UndirectedGraph<Node> + Sized,
+        W: Div<Output = W>,
+    {
+        blossom::maximum_weight_matching(self)
+    }
+}